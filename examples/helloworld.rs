@@ -1,16 +1,17 @@
 use mrgr::*;
 
-fn main() {
+fn main() -> Result<(), MrgrError> {
     let config = MrgrConfig {
         viewport_size: (320, 240),
         zoom_level: 4,
         framerate: 60,
-        caption: "Hello, World from MRGR!",
-        game_main,
+        caption: "Hello, World from MRGR!".into(),
+        game: Box::new(GameFn(game_main)),
+        ..MrgrConfig::default()
     };
-    mrgr_launch(config);
+    mrgr_launch(config.with_overrides()?)
 }
 
 fn game_main() {
-
+    while wait_event() != Event::ExitRequested {}
 }
\ No newline at end of file
@@ -0,0 +1,42 @@
+use mrgr::*;
+
+fn main() -> Result<(), MrgrError> {
+    let config = MrgrConfig {
+        viewport_size: (320, 240),
+        zoom_level: 4,
+        framerate: 60,
+        caption: "Input in MRGR".into(),
+        game: Box::new(InputDemo { last_event: None, focused: true }),
+        ..MrgrConfig::default()
+    };
+    mrgr_launch(config.with_overrides()?)
+}
+
+struct InputDemo {
+    last_event: Option<Event>,
+    focused: bool,
+}
+
+impl Game for InputDemo {
+    fn init(&mut self) {
+        load_bitmap_font("../mrgr-fontgen/noto_sans_mono_cjk_sc");
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas, _alpha: f32) {
+        let text = match &self.last_event {
+            _ if !self.focused => "Paused".to_string(),
+            Some(event) => format!("{event:?}"),
+            None => "Press a key!".to_string(),
+        };
+        canvas.nine_patch("asset/nine_patch.png", NinePatch::from_uni(6), (10, 10), (300, 40));
+        canvas.text(&text, TextStyle::SmallNormal, (20, 22), 280);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        self.last_event = Some(event.clone());
+    }
+
+    fn on_focus_change(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
@@ -0,0 +1,31 @@
+use mrgr::*;
+
+fn main() -> Result<(), MrgrError> {
+    let config = MrgrConfig {
+        viewport_size: (320, 240),
+        zoom_level: 4,
+        framerate: 60,
+        caption: "Shapes in MRGR".into(),
+        game: Box::new(GameFn(game_main)),
+        ..MrgrConfig::default()
+    };
+    mrgr_launch(config.with_overrides()?)
+}
+
+fn game_main() {
+    paint(|mut canvas| {
+        canvas.clear(0xFF1D2B53);
+        canvas.fill_rect((16, 16), (64, 40), 0xFFFF004D);
+        canvas.stroke_rect((96, 16), (64, 40), 0xFFFFEC27);
+        canvas.line((176, 16), (240, 56), 0xFF00E436);
+        canvas.circle((48, 112), 24, 0xFF29ADFF, ShapeStyle::Fill);
+        canvas.ellipse((96, 88), (64, 48), 0xFFFFA300, ShapeStyle::Stroke);
+        canvas.rounded_rect((176, 88), (64, 48), 8, 0xFF83769C, ShapeStyle::Fill);
+        canvas.polygon(&[(16, 224), (48, 160), (80, 224)], 0xFFFFCCAA, ShapeStyle::Stroke);
+        for x in 0..64 {
+            canvas.pixel((96 + x * 2, 192), 0xFFFFF1E8);
+        }
+    });
+
+    while wait_event() != Event::ExitRequested {}
+}
@@ -1,14 +1,15 @@
 use mrgr::*;
 
-fn main() {
+fn main() -> Result<(), MrgrError> {
     let config = MrgrConfig {
         viewport_size: (320, 240),
         zoom_level: 4,
         framerate: 60,
-        caption: "Text Rendering in MRGR",
-        game_main,
+        caption: "Text Rendering in MRGR".into(),
+        game: Box::new(GameFn(game_main)),
+        ..MrgrConfig::default()
     };
-    mrgr_launch(config);
+    mrgr_launch(config.with_overrides()?)
 }
 
 fn game_main() {
@@ -19,4 +20,6 @@ fn game_main() {
         dded these 没有意思的 description around it.\
         ", TextStyle::SmallNormal, (0, 0), 320);
     });
+
+    while wait_event() != Event::ExitRequested {}
 }
\ No newline at end of file
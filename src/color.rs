@@ -0,0 +1,225 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Index;
+use std::str::FromStr;
+use crate::MrgrError;
+use crate::resource::read_asset_to_string;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const TRANSPARENT: Self = Self::rgba(0, 0, 0, 0);
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const WHITE: Self = Self::rgb(255, 255, 255);
+    pub const GRAY: Self = Self::rgb(128, 128, 128);
+    pub const RED: Self = Self::rgb(255, 0, 0);
+    pub const GREEN: Self = Self::rgb(0, 255, 0);
+    pub const BLUE: Self = Self::rgb(0, 0, 255);
+    pub const YELLOW: Self = Self::rgb(255, 255, 0);
+    pub const CYAN: Self = Self::rgb(0, 255, 255);
+    pub const MAGENTA: Self = Self::rgb(255, 0, 255);
+    pub const NAVY: Self = Self::rgb(0, 0, 128);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub const fn from_argb(argb: u32) -> Self {
+        Self::rgba((argb >> 16) as u8, (argb >> 8) as u8, argb as u8, (argb >> 24) as u8)
+    }
+
+    pub const fn to_argb(self) -> u32 {
+        (self.a as u32) << 24 | (self.r as u32) << 16 | (self.g as u32) << 8 | self.b as u32
+    }
+
+    pub const fn with_alpha(self, a: u8) -> Self {
+        Self { a, ..self }
+    }
+
+    // Accepts `RGB`, `RGBA`, `RRGGBB` and `RRGGBBAA`, with or without a leading `#`.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.chars().all(|ch| ch.is_ascii_hexdigit()) { return None; }
+        let nibble = |index: usize| u8::from_str_radix(&digits[index..index + 1], 16).ok();
+        let byte = |index: usize| u8::from_str_radix(&digits[index * 2..index * 2 + 2], 16).ok();
+        match digits.len() {
+            3 => Some(Self::rgb(nibble(0)? * 17, nibble(1)? * 17, nibble(2)? * 17)),
+            4 => Some(Self::rgba(nibble(0)? * 17, nibble(1)? * 17, nibble(2)? * 17, nibble(3)? * 17)),
+            6 => Some(Self::rgb(byte(0)?, byte(1)?, byte(2)?)),
+            8 => Some(Self::rgba(byte(0)?, byte(1)?, byte(2)?, byte(3)?)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_skia(self) -> skia_safe::Color {
+        skia_safe::Color::from_argb(self.a, self.r, self.g, self.b)
+    }
+}
+
+impl From<u32> for Color {
+    fn from(value: u32) -> Self {
+        Self::from_argb(value)
+    }
+}
+
+impl FromStr for Color {
+    type Err = MrgrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s).ok_or_else(|| MrgrError::InvalidColor(s.to_string()))
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)?;
+        if self.a != 255 {
+            write!(f, "{:02X}", self.a)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Palette {
+    colors: Vec<Color>,
+}
+
+fn parse_rgb_line(line: &str) -> Option<Color> {
+    let mut channels = line.split_whitespace().map(|channel| channel.parse::<u8>().ok());
+    Some(Color::rgb(channels.next()??, channels.next()??, channels.next()??))
+}
+
+impl Palette {
+    pub fn new(colors: Vec<Color>) -> Self {
+        Self { colors }
+    }
+
+    pub fn colors(&self) -> &[Color] {
+        &self.colors
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<Color> {
+        self.colors.get(index).copied()
+    }
+
+    pub fn load(path: &str) -> Result<Self, MrgrError> {
+        let text = read_asset_to_string(path)?;
+        let parsed = if text.starts_with("GIMP Palette") {
+            Self::parse_gpl(&text)
+        } else if text.starts_with("JASC-PAL") {
+            Self::parse_jasc(&text)
+        } else {
+            Self::parse_hex_list(&text)
+        };
+        parsed.map_err(|line| MrgrError::PaletteSyntax { path: path.to_string(), line })
+    }
+
+    // The parsers report the 1-based line of the first syntax error.
+    // GIMP `.gpl`: a header, optional `Name:`/`Columns:` lines, then `R G B [name]` rows.
+    pub(crate) fn parse_gpl(text: &str) -> Result<Self, usize> {
+        let mut colors = Vec::new();
+        for (index, line) in text.lines().enumerate().skip(1) {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("Name:") || line.starts_with("Columns:") {
+                continue;
+            }
+            colors.push(parse_rgb_line(line).ok_or(index + 1)?);
+        }
+        Ok(Self::new(colors))
+    }
+
+    // JASC `.pal`: `JASC-PAL`, `0100`, the color count, then one `R G B` row per color.
+    pub(crate) fn parse_jasc(text: &str) -> Result<Self, usize> {
+        let mut lines = text.lines().map(str::trim).enumerate().skip(2);
+        let count = lines.next()
+            .and_then(|(_, line)| line.parse::<usize>().ok())
+            .ok_or(3usize)?;
+        let colors = lines
+            .filter(|(_, line)| !line.is_empty())
+            .take(count)
+            .map(|(index, line)| parse_rgb_line(line).ok_or(index + 1))
+            .collect::<Result<Vec<_>, _>>()?;
+        if colors.len() != count {
+            return Err(text.lines().count());
+        }
+        Ok(Self::new(colors))
+    }
+
+    // Lospec `.hex`: one `RRGGBB` color per line.
+    pub(crate) fn parse_hex_list(text: &str) -> Result<Self, usize> {
+        text.lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(index, line)| Color::from_hex(line).ok_or(index + 1))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::new)
+    }
+}
+
+impl Index<usize> for Palette {
+    type Output = Color;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.colors[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(Color::from_hex("#F0A"), Some(Color::rgb(0xFF, 0x00, 0xAA)));
+        assert_eq!(Color::from_hex("f0a8"), Some(Color::rgba(0xFF, 0x00, 0xAA, 0x88)));
+        assert_eq!(Color::from_hex("#1D2B53"), Some(Color::rgb(0x1D, 0x2B, 0x53)));
+        assert_eq!(Color::from_hex("1d2b5380"), Some(Color::rgba(0x1D, 0x2B, 0x53, 0x80)));
+        assert_eq!(Color::from_hex("#12345"), None);
+        assert_eq!(Color::from_hex("#GG0000"), None);
+        assert_eq!(Color::from_hex("+1+2+3"), None);
+        assert_eq!(Color::from_hex(""), None);
+    }
+
+    #[test]
+    fn gimp_palette() {
+        let text = "GIMP Palette\nName: Test\nColumns: 2\n# comment\n  0   0   0\tBlack\n255 255 255 White\n";
+        let palette = Palette::parse_gpl(text).unwrap();
+        assert_eq!(palette.colors(), &[Color::BLACK, Color::WHITE]);
+        assert_eq!(Palette::parse_gpl("GIMP Palette\n0 0 0\n255 255\n"), Err(3));
+    }
+
+    #[test]
+    fn jasc_palette() {
+        let palette = Palette::parse_jasc("JASC-PAL\n0100\n2\n255 0 0\n0 0 255\n").unwrap();
+        assert_eq!(palette.colors(), &[Color::RED, Color::BLUE]);
+        assert_eq!(Palette::parse_jasc("JASC-PAL\n0100\n3\n255 0 0\n0 0 255\n"), Err(5));
+        assert_eq!(Palette::parse_jasc("JASC-PAL\n0100\nmany\n"), Err(3));
+        assert_eq!(Palette::parse_jasc("JASC-PAL\n0100\n1\n255 0 red\n"), Err(4));
+    }
+
+    #[test]
+    fn hex_list_palette() {
+        let palette = Palette::parse_hex_list("ff0000\n\n00ff00\n").unwrap();
+        assert_eq!(palette.colors(), &[Color::RED, Color::GREEN]);
+        assert_eq!(Palette::parse_hex_list("ff0000\nnot a color\n"), Err(2));
+    }
+}
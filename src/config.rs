@@ -1,5 +1,10 @@
+use std::borrow::Cow;
+use std::time::Duration;
 use winit::dpi::PhysicalSize;
-use log::error;
+use winit::keyboard::KeyCode;
+use crate::indexed::MAXIMUM_PALETTE_SIZE;
+use crate::{Color, FullscreenMode, Game, GameFn, Keymap, MissingTexture, MrgrError, Palette};
+use crate::{PresentMode, ScalingPolicy, Settings, UpscaleFilter};
 
 pub const MAXIMUM_WIDTH: u32 = 1280;
 pub const MAXIMUM_HEIGHT: u32 = 720;
@@ -14,10 +19,8 @@ pub const MINIMUM_ZOOM_LEVEL: u32 = 1;
 pub const MAXIMUM_FRAMERATE: u32 = 480;
 pub const MINIMUM_FRAMERATE: u32 = 15;
 
-pub const fn minimum_physical_size() -> PhysicalSize<u32> {
-    PhysicalSize::new(MINIMUM_WIDTH * MINIMUM_ZOOM_LEVEL,
-                      MINIMUM_HEIGHT * MINIMUM_ZOOM_LEVEL)
-}
+pub const MAXIMUM_UPDATE_RATE: u32 = 1000;
+pub const MINIMUM_UPDATE_RATE: u32 = 1;
 
 pub const fn maximum_physical_size() -> PhysicalSize<u32> {
     PhysicalSize::new(MAXIMUM_WIDTH * MAXIMUM_ZOOM_LEVEL,
@@ -28,29 +31,94 @@ pub struct MrgrConfig {
     pub viewport_size: (u32, u32),
     pub zoom_level: u32,
     pub framerate: u32,
-    pub caption: &'static str,
-    pub game_main: fn(),
+    pub caption: Cow<'static, str>,
+    pub game: Box<dyn Game>,
+    pub keymap: Keymap,
+    pub gamepad_dead_zone: f32,
+    pub exit_timeout: Option<Duration>,
+    pub crash_screen: bool,
+    pub missing_texture: MissingTexture,
+    pub headless: bool,
+    pub present_mode: PresentMode,
+    pub update_rate: Option<u32>,
+    pub stats_overlay_key: Option<KeyCode>,
+    pub border_color: Color,
+    pub fullscreen: FullscreenMode,
+    pub scaling: ScalingPolicy,
+    pub upscale_filter: UpscaleFilter,
+    pub settings: Option<Settings>,
+    pub indexed_palette: Option<Palette>,
+}
+
+impl Default for MrgrConfig {
+    fn default() -> Self {
+        Self {
+            viewport_size: (320, 240),
+            zoom_level: 2,
+            framerate: 60,
+            caption: "MRGR".into(),
+            game: Box::new(GameFn(|| {})),
+            keymap: Keymap::default(),
+            gamepad_dead_zone: 0.15,
+            exit_timeout: Some(Duration::from_secs(5)),
+            crash_screen: true,
+            missing_texture: MissingTexture::default(),
+            headless: false,
+            present_mode: PresentMode::default(),
+            update_rate: None,
+            stats_overlay_key: Some(KeyCode::F3),
+            border_color: Color::BLACK,
+            fullscreen: FullscreenMode::default(),
+            scaling: ScalingPolicy::default(),
+            upscale_filter: UpscaleFilter::default(),
+            settings: None,
+            indexed_palette: None,
+        }
+    }
 }
 
 impl MrgrConfig {
-    pub(crate) fn ensure_sanity(&self) -> bool {
+    pub(crate) fn ensure_sanity(&self) -> Result<(), MrgrError> {
         if (self.viewport_size.0 > MAXIMUM_WIDTH) || (self.viewport_size.1 > MAXIMUM_HEIGHT) {
-            error!("Wrong configuration: viewport size is too big!");
-            return false;
+            return Err(MrgrError::invalid_config("viewport_size", format!(
+                "{:?} is too big, the maximum is {:?}",
+                self.viewport_size, (MAXIMUM_WIDTH, MAXIMUM_HEIGHT))));
         }
         if (self.viewport_size.0 < MINIMUM_WIDTH) || (self.viewport_size.1 < MINIMUM_HEIGHT) {
-            error!("Wrong configuration: viewport size is too small!");
-            return false;
+            return Err(MrgrError::invalid_config("viewport_size", format!(
+                "{:?} is too small, the minimum is {:?}",
+                self.viewport_size, (MINIMUM_WIDTH, MINIMUM_HEIGHT))));
         }
         if (self.zoom_level > MAXIMUM_ZOOM_LEVEL) || (self.zoom_level < MINIMUM_ZOOM_LEVEL) {
-            error!("Wrong configuration: zoom level is too big or too small!");
-            return false;
+            return Err(MrgrError::invalid_config("zoom_level", format!(
+                "{} is not in {MINIMUM_ZOOM_LEVEL}..={MAXIMUM_ZOOM_LEVEL}", self.zoom_level)));
         }
         if (self.framerate > MAXIMUM_FRAMERATE) || (self.framerate < MINIMUM_FRAMERATE) {
-            error!("Wrong configuration: framerate is too hig or too low!");
-            return false;
+            return Err(MrgrError::invalid_config("framerate", format!(
+                "{} is not in {MINIMUM_FRAMERATE}..={MAXIMUM_FRAMERATE}", self.framerate)));
+        }
+        if let Some(rate) = self.update_rate {
+            if !(MINIMUM_UPDATE_RATE..=MAXIMUM_UPDATE_RATE).contains(&rate) {
+                return Err(MrgrError::invalid_config("update_rate", format!(
+                    "{rate} is not in {MINIMUM_UPDATE_RATE}..={MAXIMUM_UPDATE_RATE}")));
+            }
         }
-        true
+        if !(0.0..1.0).contains(&self.gamepad_dead_zone) {
+            return Err(MrgrError::invalid_config("gamepad_dead_zone", format!(
+                "{} is not in [0, 1)", self.gamepad_dead_zone)));
+        }
+        self.upscale_filter.validate()?;
+        if let Some(palette) = &self.indexed_palette {
+            if palette.is_empty() || palette.len() > MAXIMUM_PALETTE_SIZE {
+                return Err(MrgrError::invalid_config("indexed_palette", format!(
+                    "{} colors is not in 1..={MAXIMUM_PALETTE_SIZE}", palette.len())));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn take_game(&mut self) -> Box<dyn Game> {
+        std::mem::replace(&mut self.game, Box::new(GameFn(|| {})))
     }
 
     pub(crate) fn physical_width(&self) -> u32 {
@@ -64,4 +132,9 @@ impl MrgrConfig {
     pub(crate) fn physical_size(&self) -> PhysicalSize<u32> {
         PhysicalSize::new(self.physical_width(), self.physical_height())
     }
+
+    pub(crate) fn minimum_physical_size(&self) -> PhysicalSize<u32> {
+        PhysicalSize::new(self.viewport_size.0 * MINIMUM_ZOOM_LEVEL,
+                          self.viewport_size.1 * MINIMUM_ZOOM_LEVEL)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,115 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use serde::{Deserialize, Serialize};
+use winit::dpi::PhysicalSize;
+use winit::window::{Fullscreen, Window};
+use crate::{config, MrgrError};
+use crate::runtime::{runtime, RuntimeEvent};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FullscreenMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Exclusive,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScalingPolicy {
+    #[default]
+    Integer,
+    Fit,
+    Stretch,
+}
+
+impl FullscreenMode {
+    pub(crate) fn to_winit(self, window: &Window) -> Option<Fullscreen> {
+        match self {
+            Self::Windowed => None,
+            Self::Borderless => Some(Fullscreen::Borderless(window.current_monitor())),
+            Self::Exclusive => {
+                let mode = window.current_monitor()?
+                    .video_modes()
+                    .max_by_key(|mode| {
+                        let size = mode.size();
+                        (size.width * size.height, mode.refresh_rate_millihertz())
+                    });
+                match mode {
+                    Some(mode) => Some(Fullscreen::Exclusive(mode)),
+                    None => Some(Fullscreen::Borderless(window.current_monitor())),
+                }
+            }
+        }
+    }
+}
+
+impl ScalingPolicy {
+    pub(crate) fn bounds(
+        self,
+        viewport_size: (u32, u32),
+        zoom_range: (u32, u32),
+        size: PhysicalSize<u32>
+    ) -> skia_safe::Rect {
+        let (v_width, v_height) = viewport_size;
+        let (p_width, p_height) = match self {
+            Self::Integer => {
+                let zoom = (size.width / v_width).min(size.height / v_height)
+                    .clamp(zoom_range.0, zoom_range.1);
+                ((v_width * zoom) as f32, (v_height * zoom) as f32)
+            }
+            Self::Fit => {
+                let scale = (size.width as f32 / v_width as f32)
+                    .min(size.height as f32 / v_height as f32);
+                ((v_width as f32 * scale).floor(), (v_height as f32 * scale).floor())
+            }
+            Self::Stretch => (size.width as f32, size.height as f32),
+        };
+        let left = ((size.width as f32 - p_width) / 2.0).floor();
+        let top = ((size.height as f32 - p_height) / 2.0).floor();
+        skia_safe::Rect::from_xywh(left, top, p_width, p_height)
+    }
+}
+
+pub(crate) struct DisplayState {
+    zoom_level: AtomicU32,
+    maximum_zoom_level: AtomicU32,
+}
+
+impl DisplayState {
+    pub fn new(zoom_level: u32) -> Self {
+        Self {
+            zoom_level: AtomicU32::new(zoom_level),
+            maximum_zoom_level: AtomicU32::new(config::MAXIMUM_ZOOM_LEVEL),
+        }
+    }
+
+    pub fn zoom_level(&self) -> u32 {
+        self.zoom_level.load(Ordering::Relaxed)
+    }
+
+    pub fn limit_to_monitor(&self, viewport_size: (u32, u32), monitor_size: PhysicalSize<u32>) {
+        let fits = (monitor_size.width / viewport_size.0).min(monitor_size.height / viewport_size.1)
+            .clamp(config::MINIMUM_ZOOM_LEVEL, config::MAXIMUM_ZOOM_LEVEL);
+        self.maximum_zoom_level.store(fits, Ordering::Relaxed);
+    }
+}
+
+pub fn zoom_level() -> u32 {
+    runtime().display.zoom_level()
+}
+
+pub fn maximum_zoom_level() -> u32 {
+    runtime().display.maximum_zoom_level.load(Ordering::Relaxed)
+}
+
+pub fn set_zoom_level(zoom_level: u32) -> Result<(), MrgrError> {
+    let maximum = maximum_zoom_level();
+    if !(config::MINIMUM_ZOOM_LEVEL..=maximum).contains(&zoom_level) {
+        return Err(MrgrError::invalid_config("zoom_level", format!(
+            "{zoom_level} is not in {}..={maximum}", config::MINIMUM_ZOOM_LEVEL)));
+    }
+    runtime().display.zoom_level.store(zoom_level, Ordering::Relaxed);
+    runtime().proxy.send(RuntimeEvent::ZoomRequested(zoom_level));
+    Ok(())
+}
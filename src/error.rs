@@ -0,0 +1,96 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use winit::error::{EventLoopError, OsError};
+
+#[derive(Debug)]
+pub enum MrgrError {
+    InvalidConfig { field: &'static str, reason: String },
+    ConfigSyntax { path: String, message: String },
+    NoUserDirectory,
+    UnsupportedSchema { path: String, version: i64 },
+    SettingsEncoding(String),
+    EventLoop(EventLoopError),
+    WindowCreation(OsError),
+    Softbuffer(softbuffer::SoftBufferError),
+    SurfaceCreation(&'static str),
+    AssetIo { path: String, source: io::Error },
+    AssetDecoding { path: String },
+    AssetEncoding { path: String },
+    ImageSizeMismatch { expected: (u32, u32), actual: (u32, u32) },
+    ViewportReadback,
+    GamePanicked(String),
+    ExitTimeout,
+    RuntimeActive,
+    InvalidSlotName(String),
+    SaveCorrupted { slot: String, reason: &'static str },
+    InvalidColor(String),
+    PaletteSyntax { path: String, line: usize },
+}
+
+impl MrgrError {
+    pub(crate) fn invalid_config(field: &'static str, reason: impl Into<String>) -> Self {
+        Self::InvalidConfig { field, reason: reason.into() }
+    }
+}
+
+impl Display for MrgrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidConfig { field, reason } =>
+                write!(f, "invalid configuration for `{field}`: {reason}"),
+            Self::ConfigSyntax { path, message } =>
+                write!(f, "failed to parse configuration file `{path}`: {message}"),
+            Self::NoUserDirectory => write!(f, "no user configuration or data directory on this platform"),
+            Self::UnsupportedSchema { path, version } =>
+                write!(f, "`{path}` uses schema version {version}, which is newer than this build supports"),
+            Self::SettingsEncoding(message) => write!(f, "failed to encode settings: {message}"),
+            Self::EventLoop(err) => write!(f, "event loop failure: {err}"),
+            Self::WindowCreation(err) => write!(f, "failed to create the window: {err}"),
+            Self::Softbuffer(err) => write!(f, "softbuffer failure: {err}"),
+            Self::SurfaceCreation(which) => write!(f, "failed to create the {which} surface"),
+            Self::AssetIo { path, source } => write!(f, "I/O failure on asset `{path}`: {source}"),
+            Self::AssetDecoding { path } => write!(f, "failed to decode asset `{path}`"),
+            Self::AssetEncoding { path } => write!(f, "failed to encode image for `{path}`"),
+            Self::ImageSizeMismatch { expected, actual } =>
+                write!(f, "image size {actual:?} does not match the expected {expected:?}"),
+            Self::ViewportReadback => write!(f, "failed to read back the viewport pixels"),
+            Self::GamePanicked(message) => write!(f, "game thread panicked: {message}"),
+            Self::ExitTimeout => write!(f, "game thread did not exit in time and was abandoned"),
+            Self::RuntimeActive => write!(f, "a runtime is already running in this process"),
+            Self::InvalidSlotName(name) => write!(f, "`{name}` is not a valid save slot name"),
+            Self::SaveCorrupted { slot, reason } => write!(f, "save slot `{slot}` is corrupted: {reason}"),
+            Self::InvalidColor(text) => write!(f, "`{text}` is not a valid hex color"),
+            Self::PaletteSyntax { path, line } => write!(f, "malformed palette `{path}` at line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for MrgrError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::EventLoop(err) => Some(err),
+            Self::WindowCreation(err) => Some(err),
+            Self::Softbuffer(err) => Some(err),
+            Self::AssetIo { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<EventLoopError> for MrgrError {
+    fn from(value: EventLoopError) -> Self {
+        Self::EventLoop(value)
+    }
+}
+
+impl From<OsError> for MrgrError {
+    fn from(value: OsError) -> Self {
+        Self::WindowCreation(value)
+    }
+}
+
+impl From<softbuffer::SoftBufferError> for MrgrError {
+    fn from(value: softbuffer::SoftBufferError) -> Self {
+        Self::Softbuffer(value)
+    }
+}
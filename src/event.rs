@@ -1,6 +1,11 @@
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::runtime::runtime;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-enum Key {
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Key {
     Up,
     Down,
     Left,
@@ -20,14 +25,14 @@ enum Key {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-enum Mouse {
+pub enum Mouse {
     Main,
     Secondary,
     Tertiary,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-enum Axis {
+pub enum Axis {
     LeftStickX,
     LeftStickY,
     RightStickX,
@@ -37,12 +42,91 @@ enum Axis {
 }
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
-enum Event {
+pub enum Event {
     ExitRequested,
     KeyPressed(Key),
     KeyReleased(Key),
     MousePressed(Mouse),
     MouseReleased(Mouse),
     MouseMoved(u32, u32),
+    MouseWheel(f32, f32),
     AxisChanged(Axis, f32),
-}
\ No newline at end of file
+    GamepadConnected(usize),
+    GamepadDisconnected(usize),
+    FocusChanged(bool),
+}
+
+#[derive(Default)]
+struct QueueState {
+    events: VecDeque<Event>,
+    waiting: usize,
+}
+
+pub(crate) struct EventQueue {
+    state: Mutex<QueueState>,
+    available: Condvar,
+}
+
+impl EventQueue {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(QueueState::default()),
+            available: Condvar::new(),
+        }
+    }
+
+    pub fn push(&self, event: Event) {
+        self.state.lock().unwrap().events.push_back(event);
+        self.available.notify_all();
+    }
+
+    pub fn poll(&self) -> Option<Event> {
+        self.state.lock().unwrap().events.pop_front()
+    }
+
+    pub fn wait(&self) -> Event {
+        let mut state = self.state.lock().unwrap();
+        state.waiting += 1;
+        loop {
+            if let Some(event) = state.events.pop_front() {
+                state.waiting -= 1;
+                return event;
+            }
+            state = self.available.wait(state).unwrap();
+        }
+    }
+
+    pub fn wait_timeout(&self, timeout: Duration) -> Option<Event> {
+        let state = self.state.lock().unwrap();
+        let (mut state, _) = self.available
+            .wait_timeout_while(state, timeout, |state| state.events.is_empty())
+            .unwrap();
+        state.events.pop_front()
+    }
+
+    pub fn clear(&self) {
+        self.state.lock().unwrap().events.clear();
+    }
+
+    // True while a thread is blocked in `wait` with nothing queued, so only a new event can wake it.
+    pub fn is_idle(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.waiting > 0 && state.events.is_empty()
+    }
+}
+
+pub fn poll_event() -> Option<Event> {
+    runtime().events.poll()
+}
+
+pub fn wait_event() -> Event {
+    runtime().events.wait()
+}
+
+pub fn wait_event_timeout(timeout: Duration) -> Option<Event> {
+    runtime().events.wait_timeout(timeout)
+}
+
+pub fn clear_events() {
+    runtime().events.clear();
+}
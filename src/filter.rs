@@ -0,0 +1,204 @@
+use std::sync::Mutex;
+use crate::{Image, MrgrError};
+use crate::runtime::runtime;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum UpscaleFilter {
+    #[default]
+    Nearest,
+    Bilinear,
+    SharpBilinear,
+    Scale2x,
+    Crt { scanlines: f32, shadow_mask: bool },
+}
+
+impl UpscaleFilter {
+    pub(crate) fn validate(&self) -> Result<(), MrgrError> {
+        if let Self::Crt { scanlines, .. } = self {
+            if !(0.0..=1.0).contains(scanlines) {
+                return Err(MrgrError::invalid_config("upscale_filter", format!(
+                    "scanline intensity {scanlines} is not in [0, 1]")));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct FilterState {
+    filter: Mutex<UpscaleFilter>,
+}
+
+impl FilterState {
+    pub fn new(filter: UpscaleFilter) -> Self {
+        Self { filter: Mutex::new(filter) }
+    }
+
+    pub fn get(&self) -> UpscaleFilter {
+        *self.filter.lock().unwrap()
+    }
+}
+
+pub fn upscale_filter() -> UpscaleFilter {
+    runtime().filter.get()
+}
+
+pub fn set_upscale_filter(filter: UpscaleFilter) -> Result<(), MrgrError> {
+    filter.validate()?;
+    *runtime().filter.filter.lock().unwrap() = filter;
+    Ok(())
+}
+
+fn sampling(mode: skia_safe::FilterMode) -> skia_safe::SamplingOptions {
+    skia_safe::SamplingOptions::new(mode, skia_safe::MipmapMode::None)
+}
+
+fn draw_sampled(
+    canvas: &skia_safe::Canvas,
+    image: &skia_safe::Image,
+    bounds: &skia_safe::Rect,
+    mode: skia_safe::FilterMode
+) {
+    canvas.draw_image_rect_with_sampling_options(
+        image, None, bounds, sampling(mode), &skia_safe::Paint::default());
+}
+
+fn integer_scale(image: &skia_safe::Image, bounds: &skia_safe::Rect) -> i32 {
+    let scale_x = bounds.width() / image.width() as f32;
+    let scale_y = bounds.height() / image.height() as f32;
+    (scale_x.min(scale_y).floor() as i32).max(1)
+}
+
+fn prescale_nearest(image: &skia_safe::Image, factor: i32) -> Option<skia_safe::Image> {
+    let mut surface = skia_safe::surfaces::raster_n32_premul(
+        (image.width() * factor, image.height() * factor))?;
+    let dst = skia_safe::Rect::from_iwh(image.width() * factor, image.height() * factor);
+    draw_sampled(surface.canvas(), image, &dst, skia_safe::FilterMode::Nearest);
+    Some(surface.image_snapshot())
+}
+
+fn scale2x(image: &Image) -> Image {
+    let (width, height) = (image.width(), image.height());
+    let mut pixels = vec![0u8; width as usize * height as usize * 16];
+    let at = |x: i64, y: i64| {
+        image.pixel(x.clamp(0, width as i64 - 1) as u32, y.clamp(0, height as i64 - 1) as u32)
+    };
+    let mut put = |x: u32, y: u32, pixel: [u8; 4]| {
+        let index = (y as usize * width as usize * 2 + x as usize) * 4;
+        pixels[index..index + 4].copy_from_slice(&pixel);
+    };
+    for y in 0..height {
+        for x in 0..width {
+            let (xi, yi) = (x as i64, y as i64);
+            let p = at(xi, yi);
+            let a = at(xi, yi - 1);
+            let b = at(xi + 1, yi);
+            let c = at(xi - 1, yi);
+            let d = at(xi, yi + 1);
+            let e0 = if c == a && c != d && a != b { a } else { p };
+            let e1 = if a == b && a != c && b != d { b } else { p };
+            let e2 = if d == c && d != b && c != a { c } else { p };
+            let e3 = if b == d && b != a && d != c { d } else { p };
+            put(x * 2, y * 2, e0);
+            put(x * 2 + 1, y * 2, e1);
+            put(x * 2, y * 2 + 1, e2);
+            put(x * 2 + 1, y * 2 + 1, e3);
+        }
+    }
+    Image::new(width * 2, height * 2, pixels)
+}
+
+fn crt_mask(row_height: i32, scanlines: f32, shadow_mask: bool) -> Option<skia_safe::Image> {
+    let columns: &[[u8; 3]] = if shadow_mask {
+        &[[255, 200, 200], [200, 255, 200], [200, 200, 255]]
+    } else {
+        &[[255, 255, 255]]
+    };
+    let dark_rows = (row_height / 3).max(1);
+    let mut pixels = Vec::with_capacity(columns.len() * row_height as usize * 4);
+    for y in 0..row_height {
+        let shade = if row_height > 1 && y >= row_height - dark_rows { 1.0 - scanlines } else { 1.0 };
+        for column in columns {
+            pixels.extend(column.iter().map(|channel| (*channel as f32 * shade) as u8));
+            pixels.push(255);
+        }
+    }
+    Image::new(columns.len() as u32, row_height as u32, pixels).to_skia()
+}
+
+// Keeps CPU-side filter results between presents. Scale2x output is keyed by the unique id of the
+// presented image, which only changes when a new frame is presented or the palette changes.
+#[derive(Default)]
+pub(crate) struct FilterCache {
+    scaled: Option<(u32, i32, skia_safe::Image)>,
+    mask: Option<((i32, u32, bool), skia_safe::Image)>,
+}
+
+impl FilterCache {
+    fn scale2x(&mut self, image: &skia_safe::Image, target: i32) -> Option<skia_safe::Image> {
+        if let Some((id, cached_target, scaled)) = &self.scaled {
+            if *id == image.unique_id() && *cached_target == target {
+                return Some(scaled.clone());
+            }
+        }
+        let mut scaled = Image::from_skia(image)?;
+        let mut factor = 2;
+        while factor <= target && factor <= 4 {
+            scaled = scale2x(&scaled);
+            factor *= 2;
+        }
+        let scaled = scaled.to_skia()?;
+        self.scaled = Some((image.unique_id(), target, scaled.clone()));
+        Some(scaled)
+    }
+
+    fn crt_mask(&mut self, row_height: i32, scanlines: f32, shadow_mask: bool) -> Option<skia_safe::Image> {
+        let key = (row_height, scanlines.to_bits(), shadow_mask);
+        if let Some((cached_key, mask)) = &self.mask {
+            if *cached_key == key {
+                return Some(mask.clone());
+            }
+        }
+        let mask = crt_mask(row_height, scanlines, shadow_mask)?;
+        self.mask = Some((key, mask.clone()));
+        Some(mask)
+    }
+}
+
+pub(crate) fn draw_upscaled(
+    canvas: &skia_safe::Canvas,
+    image: &skia_safe::Image,
+    bounds: &skia_safe::Rect,
+    filter: UpscaleFilter,
+    cache: &mut FilterCache
+) {
+    match filter {
+        UpscaleFilter::Nearest => draw_sampled(canvas, image, bounds, skia_safe::FilterMode::Nearest),
+        UpscaleFilter::Bilinear => draw_sampled(canvas, image, bounds, skia_safe::FilterMode::Linear),
+        UpscaleFilter::SharpBilinear => {
+            match prescale_nearest(image, integer_scale(image, bounds)) {
+                Some(prescaled) => draw_sampled(canvas, &prescaled, bounds, skia_safe::FilterMode::Linear),
+                None => draw_sampled(canvas, image, bounds, skia_safe::FilterMode::Nearest),
+            }
+        }
+        UpscaleFilter::Scale2x => {
+            match cache.scale2x(image, integer_scale(image, bounds)) {
+                Some(scaled) => draw_sampled(canvas, &scaled, bounds, skia_safe::FilterMode::Nearest),
+                None => draw_sampled(canvas, image, bounds, skia_safe::FilterMode::Nearest),
+            }
+        }
+        UpscaleFilter::Crt { scanlines, shadow_mask } => {
+            draw_sampled(canvas, image, bounds, skia_safe::FilterMode::Nearest);
+            let row_height = (bounds.height() / image.height() as f32).round() as i32;
+            let Some(mask) = cache.crt_mask(row_height.max(1), scanlines, shadow_mask) else { return; };
+            let offset = skia_safe::Matrix::translate((bounds.left, bounds.top));
+            let mut paint = skia_safe::Paint::default();
+            paint.set_shader(mask.to_shader(
+                (skia_safe::TileMode::Repeat, skia_safe::TileMode::Repeat),
+                sampling(skia_safe::FilterMode::Nearest),
+                &offset
+            ));
+            paint.set_blend_mode(skia_safe::BlendMode::Multiply);
+            canvas.draw_rect(bounds, &paint);
+        }
+    }
+}
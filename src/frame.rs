@@ -0,0 +1,158 @@
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+use crate::{present, Canvas};
+use crate::graphics::paint_uncommitted;
+use crate::runtime::runtime;
+
+#[derive(Default)]
+struct FrameState {
+    count: u64,
+    time: Duration,
+    consumed: u64,
+    consumed_time: Duration,
+    submitted: u64,
+    synchronized: bool,
+    shutdown: bool,
+}
+
+pub(crate) struct FrameClock {
+    state: Mutex<FrameState>,
+    changed: Condvar,
+}
+
+impl FrameClock {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(FrameState::default()),
+            changed: Condvar::new(),
+        }
+    }
+
+    pub fn tick(&self, time: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.count += 1;
+        state.time = time;
+        self.changed.notify_all();
+    }
+
+    pub fn next(&self) -> Duration {
+        let mut state = self.state.lock().unwrap();
+        state.synchronized = true;
+        while state.count <= state.consumed && !state.shutdown {
+            state = self.changed.wait(state).unwrap();
+        }
+        let delta = state.time - state.consumed_time;
+        state.consumed = state.count;
+        state.consumed_time = state.time;
+        delta
+    }
+
+    pub fn submit(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.submitted = state.consumed;
+        self.changed.notify_all();
+    }
+
+    pub fn shutdown(&self) {
+        self.state.lock().unwrap().shutdown = true;
+        self.changed.notify_all();
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.state.lock().unwrap().shutdown
+    }
+
+    pub fn is_synchronized(&self) -> bool {
+        self.state.lock().unwrap().synchronized
+    }
+
+    pub fn consumed(&self) -> u64 {
+        self.state.lock().unwrap().consumed
+    }
+
+    pub fn wait_submitted(&self, timeout: Duration) -> bool {
+        let state = self.state.lock().unwrap();
+        let (state, _) = self.changed
+            .wait_timeout_while(state, timeout, |state| {
+                state.synchronized && state.submitted < state.count
+            })
+            .unwrap();
+        !state.synchronized || state.submitted >= state.count
+    }
+}
+
+// Input is latched when the game consumes a frame, so edges between skipped ticks are kept.
+pub fn next_frame() -> Duration {
+    let delta = runtime().frames.next();
+    runtime().input.latch();
+    delta
+}
+
+pub fn frame_count() -> u64 {
+    runtime().frames.consumed()
+}
+
+pub fn submit_frame() {
+    runtime().frames.submit();
+}
+
+pub fn run_loop<F: FnMut(&mut Canvas, Duration) -> bool>(mut func: F) {
+    loop {
+        let delta = next_frame();
+        let mut running = true;
+        paint_uncommitted(|mut canvas| running = func(&mut canvas, delta));
+        present();
+        submit_frame();
+        if !running || runtime().frames.is_shutdown() { break; }
+    }
+}
+
+const MAXIMUM_UPDATES_PER_FRAME: u32 = 8;
+
+pub fn update_step() -> Duration {
+    runtime().update_step
+}
+
+// Accumulates frame time and runs the fixed updates it covers; shared by `run_fixed_loop` and `Game`.
+pub(crate) struct FixedStep {
+    step: Duration,
+    accumulator: Duration,
+}
+
+impl FixedStep {
+    pub fn new() -> Self {
+        Self { step: update_step(), accumulator: Duration::ZERO }
+    }
+
+    // Returns how far the frame is into the next step, for interpolation.
+    pub fn advance(&mut self, delta: Duration, mut update: impl FnMut(Duration)) -> f32 {
+        self.accumulator += delta;
+        let mut updates = 0;
+        while self.accumulator >= self.step {
+            update(self.step);
+            self.accumulator -= self.step;
+            updates += 1;
+            if updates >= MAXIMUM_UPDATES_PER_FRAME {
+                self.accumulator = Duration::ZERO;
+                break;
+            }
+        }
+        self.accumulator.as_secs_f32() / self.step.as_secs_f32()
+    }
+}
+
+pub fn run_fixed_loop<U, D>(mut update: U, mut draw: D)
+where
+    U: FnMut(Duration),
+    D: FnMut(&mut Canvas, f32) -> bool,
+{
+    let mut stepper = FixedStep::new();
+    loop {
+        let alpha = stepper.advance(next_frame(), &mut update);
+        let mut running = true;
+        paint_uncommitted(|mut canvas| running = draw(&mut canvas, alpha));
+        present();
+        submit_frame();
+        if !running || runtime().frames.is_shutdown() { break; }
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+use crate::{next_frame, poll_event, present, submit_frame, Canvas, Event};
+use crate::frame::FixedStep;
+use crate::graphics::paint_uncommitted;
+use crate::runtime::runtime;
+
+pub trait Game: Send + 'static {
+    fn init(&mut self) {}
+
+    fn update(&mut self, _step: Duration) {}
+
+    fn draw(&mut self, _canvas: &mut Canvas, _alpha: f32) {}
+
+    fn on_event(&mut self, _event: &Event) {}
+
+    fn on_focus_change(&mut self, _focused: bool) {}
+
+    fn on_exit(&mut self) {}
+
+    fn run(&mut self) {
+        drive(self);
+    }
+}
+
+pub struct GameFn(pub fn());
+
+impl Game for GameFn {
+    fn run(&mut self) {
+        (self.0)();
+    }
+}
+
+fn drive<G: Game + ?Sized>(game: &mut G) {
+    game.init();
+    let mut stepper = FixedStep::new();
+    loop {
+        let delta = next_frame();
+        let mut exiting = false;
+        while let Some(event) = poll_event() {
+            game.on_event(&event);
+            match event {
+                Event::FocusChanged(focused) => game.on_focus_change(focused),
+                Event::ExitRequested => exiting = true,
+                _ => {}
+            }
+        }
+        if exiting || runtime().frames.is_shutdown() {
+            game.on_exit();
+            submit_frame();
+            break;
+        }
+        let alpha = stepper.advance(delta, |step| game.update(step));
+        paint_uncommitted(|mut canvas| game.draw(&mut canvas, alpha));
+        present();
+        submit_frame();
+    }
+}
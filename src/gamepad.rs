@@ -0,0 +1,166 @@
+use std::collections::{BTreeSet, HashMap};
+use std::thread;
+use gilrs::{Button, EventType, GamepadId, Gilrs};
+use log::{error, info};
+use crate::{Axis, Event, Key};
+use crate::runtime::dispatch;
+
+fn map_button(button: Button) -> Option<Key> {
+    match button {
+        Button::South => Some(Key::South),
+        Button::East => Some(Key::East),
+        Button::North => Some(Key::North),
+        Button::West => Some(Key::West),
+        Button::LeftTrigger => Some(Key::LeftBumper),
+        Button::RightTrigger => Some(Key::RightBumper),
+        Button::LeftTrigger2 => Some(Key::LeftTrigger),
+        Button::RightTrigger2 => Some(Key::RightTrigger),
+        Button::Select => Some(Key::Select),
+        Button::Start => Some(Key::Start),
+        Button::LeftThumb => Some(Key::LeftThumb),
+        Button::RightThumb => Some(Key::RightThumb),
+        Button::DPadUp => Some(Key::Up),
+        Button::DPadDown => Some(Key::Down),
+        Button::DPadLeft => Some(Key::Left),
+        Button::DPadRight => Some(Key::Right),
+        _ => None,
+    }
+}
+
+fn map_axis(axis: gilrs::Axis) -> Option<Axis> {
+    match axis {
+        gilrs::Axis::LeftStickX => Some(Axis::LeftStickX),
+        gilrs::Axis::LeftStickY => Some(Axis::LeftStickY),
+        gilrs::Axis::RightStickX => Some(Axis::RightStickX),
+        gilrs::Axis::RightStickY => Some(Axis::RightStickY),
+        _ => None,
+    }
+}
+
+fn map_trigger(button: Button) -> Option<Axis> {
+    match button {
+        Button::LeftTrigger2 => Some(Axis::LeftTrigger),
+        Button::RightTrigger2 => Some(Axis::RightTrigger),
+        _ => None,
+    }
+}
+
+fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value.abs() < dead_zone { 0.0 } else { value }
+}
+
+struct AxisFilter {
+    dead_zone: f32,
+    values: HashMap<(GamepadId, Axis), f32>,
+}
+
+impl AxisFilter {
+    fn update(&mut self, id: GamepadId, axis: Axis, value: f32) {
+        let value = apply_dead_zone(value, self.dead_zone);
+        if self.values.insert((id, axis), value) != Some(value) {
+            dispatch(Event::AxisChanged(axis, value));
+        }
+    }
+
+    fn forget(&mut self, id: GamepadId) {
+        let axes = self.values.keys()
+            .filter(|(gamepad, _)| *gamepad == id)
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in axes {
+            if self.values.remove(&key) != Some(0.0) {
+                dispatch(Event::AxisChanged(key.1, 0.0));
+            }
+        }
+    }
+}
+
+// Held buttons are tracked per gamepad so a disconnect can release them.
+#[derive(Default)]
+struct ButtonTracker {
+    held: HashMap<GamepadId, BTreeSet<Key>>,
+}
+
+impl ButtonTracker {
+    fn press(&mut self, id: GamepadId, key: Key) {
+        self.held.entry(id).or_default().insert(key);
+        dispatch(Event::KeyPressed(key));
+    }
+
+    fn release(&mut self, id: GamepadId, key: Key) {
+        if let Some(keys) = self.held.get_mut(&id) {
+            keys.remove(&key);
+        }
+        dispatch(Event::KeyReleased(key));
+    }
+
+    fn forget(&mut self, id: GamepadId) {
+        for key in self.held.remove(&id).unwrap_or_default() {
+            dispatch(Event::KeyReleased(key));
+        }
+    }
+}
+
+fn gamepad_main(mut gilrs: Gilrs, dead_zone: f32) {
+    let mut filter = AxisFilter {
+        dead_zone,
+        values: HashMap::new(),
+    };
+    let mut buttons = ButtonTracker::default();
+    for (id, gamepad) in gilrs.gamepads() {
+        info!("Gamepad {} found: {}", usize::from(id), gamepad.name());
+        dispatch(Event::GamepadConnected(usize::from(id)));
+    }
+    loop {
+        let Some(gilrs::Event { id, event, .. }) = gilrs.next_event_blocking(None) else {
+            continue;
+        };
+        match event {
+            EventType::ButtonPressed(button, _) => {
+                if let Some(key) = map_button(button) {
+                    buttons.press(id, key);
+                }
+            }
+            EventType::ButtonReleased(button, _) => {
+                if let Some(key) = map_button(button) {
+                    buttons.release(id, key);
+                }
+            }
+            EventType::ButtonChanged(button, value, _) => {
+                if let Some(axis) = map_trigger(button) {
+                    filter.update(id, axis, value);
+                }
+            }
+            EventType::AxisChanged(axis, value, _) => {
+                if let Some(axis) = map_axis(axis) {
+                    filter.update(id, axis, value);
+                }
+            }
+            EventType::Connected => {
+                info!("Gamepad {} connected: {}", usize::from(id), gilrs.gamepad(id).name());
+                dispatch(Event::GamepadConnected(usize::from(id)));
+            }
+            EventType::Disconnected => {
+                info!("Gamepad {} disconnected", usize::from(id));
+                filter.forget(id);
+                buttons.forget(id);
+                dispatch(Event::GamepadDisconnected(usize::from(id)));
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn spawn_gamepad_thread(dead_zone: f32) {
+    let result = thread::Builder::new()
+        .name("mrgr-gamepad".to_string())
+        .spawn(move || {
+            match Gilrs::new() {
+                Ok(gilrs) => gamepad_main(gilrs, dead_zone),
+                Err(err) => error!("Gamepad support is unavailable: {err}"),
+            }
+        });
+    if let Err(err) = result {
+        error!("Failed to spawn the gamepad thread: {err}");
+    }
+}
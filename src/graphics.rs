@@ -1,6 +1,6 @@
 use std::sync::MutexGuard;
+use std::time::Instant;
 use log::info;
-use crate::is_font_loaded;
 use crate::runtime::runtime;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -63,21 +63,122 @@ impl NinePatch {
 }
 
 pub struct Canvas {
-    pub(crate) surface: MutexGuard<'static, skia_safe::Surface>
+    pub(crate) surface: MutexGuard<'static, skia_safe::Surface>,
+    pub(crate) antialias: bool,
 }
 
-pub fn paint<F: FnOnce(Canvas)>(func: F) {
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PresentMode {
+    #[default]
+    Continuous,
+    OnCommit,
+}
+
+pub(crate) fn paint_uncommitted<F: FnOnce(Canvas)>(func: F) {
+    let began = Instant::now();
     let canvas = Canvas {
-        surface: runtime().surface.lock().unwrap()
+        surface: runtime().surface.lock().unwrap(),
+        antialias: false,
     };
     func(canvas);
+    runtime().stats.record_paint(began.elapsed());
+}
+
+// Games that never call `next_frame` get their input latched here, once per `paint`.
+pub fn paint<F: FnOnce(Canvas)>(func: F) {
+    if !runtime().frames.is_synchronized() {
+        runtime().input.latch();
+    }
+    paint_uncommitted(func);
+    if runtime().present_mode == PresentMode::Continuous {
+        present();
+    }
+}
+
+pub fn present() {
+    let image = runtime().surface.lock().unwrap().image_snapshot();
+    let mut front = runtime().front.lock().unwrap();
+    front.image = image;
+    front.serial += 1;
+    if let Some(indexed) = &runtime().indexed {
+        indexed.present();
+    }
+}
+
+pub(crate) fn draw_text(
+    canvas: &skia_safe::Canvas,
+    text: &str,
+    style: TextStyle,
+    pos: (i32, i32),
+    line_width: i32
+) {
+    let Some(font) = crate::font() else { return; };
+    let full_width = if style.is_small() { 16 } else { 32 };
+
+    let mut x = pos.0;
+    let mut y = pos.1;
+    for ch in text.chars() {
+        if ch == '\r' { continue; }
+        if ch == '\n' {
+            x = pos.0;
+            y += full_width;
+            continue;
+        }
+        let result = font.lookup_or_default(ch);
+        let width = if result.is_full_width { full_width } else { full_width / 2 };
+        if x + width > pos.0 + line_width {
+            x = pos.0;
+            y += full_width;
+        }
+        let bitmap = &font.bitmaps[&style];
+        let src = skia_safe::Rect::from_xywh(
+            (result.pos.0 * full_width) as f32,
+            (result.pos.1 * full_width) as f32,
+            width as f32,
+            full_width as f32
+        );
+        let dst = skia_safe::Rect::from_xywh(
+            x as f32,
+            y as f32,
+            width as f32,
+            full_width as f32
+        );
+        x += width;
+        canvas.draw_image_rect(
+            bitmap,
+            Some((&src, skia_safe::canvas::SrcRectConstraint::Fast)),
+            dst,
+            &skia_safe::Paint::default()
+        );
+    }
 }
 
 impl Canvas {
+    fn missing(&mut self, pos: (i32, i32), size: (i32, i32)) {
+        let canvas = self.surface.canvas();
+        let offset = skia_safe::Matrix::translate((pos.0 as f32, pos.1 as f32));
+        let shader = crate::missing_texture().to_shader(
+            (skia_safe::TileMode::Repeat, skia_safe::TileMode::Repeat),
+            skia_safe::SamplingOptions::default(),
+            &offset
+        );
+        let mut paint = skia_safe::Paint::default();
+        paint.set_shader(shader);
+        canvas.draw_rect(
+            skia_safe::Rect::from_xywh(pos.0 as f32, pos.1 as f32, size.0 as f32, size.1 as f32),
+            &paint
+        );
+    }
+
     pub fn image(&mut self, path: &str, pos: (i32, i32), size: impl Into<Option<(i32, i32)>>) {
+        let size = size.into();
+        let Some(image) = crate::image(path) else {
+            let placeholder = crate::missing_texture();
+            self.missing(pos, size.unwrap_or((placeholder.width(), placeholder.height())));
+            return;
+        };
         let canvas = self.surface.canvas();
-        let image = crate::image(path);
-        let size = size.into().unwrap_or((image.width(), image.height()));
+        let size = size.unwrap_or((image.width(), image.height()));
         let src = skia_safe::Rect::from_xywh(0.0, 0.0, image.width() as f32, image.height() as f32);
         let dst = skia_safe::Rect::from_xywh(pos.0 as f32, pos.1 as f32, size.0 as f32, size.1 as f32);
         canvas.draw_image_rect(
@@ -89,52 +190,15 @@ impl Canvas {
     }
 
     pub fn text(&mut self, text: &str, style: TextStyle, pos: (i32, i32), line_width: i32) {
-        let canvas = self.surface.canvas();
-        if !is_font_loaded() { return; }
-        let font = crate::font();
-        let full_width = if style.is_small() { 16 } else { 32 };
-
-        let mut x = pos.0;
-        let mut y = pos.1;
-        for ch in text.chars() {
-            if ch == '\r' { continue; }
-            if ch == '\n' {
-                x = pos.0;
-                y += full_width;
-                continue;
-            }
-            let result = font.lookup_or_default(ch);
-            let width = if result.is_full_width { full_width } else { full_width / 2 };
-            if x + width > pos.0 + line_width {
-                x = pos.0;
-                y += full_width;
-            }
-            let bitmap = &font.bitmaps[&style];
-            let src = skia_safe::Rect::from_xywh(
-                (result.pos.0 * full_width) as f32,
-                (result.pos.1 * full_width) as f32,
-                width as f32,
-                full_width as f32
-            );
-            let dst = skia_safe::Rect::from_xywh(
-                x as f32,
-                y as f32,
-                width as f32,
-                full_width as f32
-            );
-            x += width;
-            canvas.draw_image_rect(
-                bitmap,
-                Some((&src, skia_safe::canvas::SrcRectConstraint::Fast)),
-                dst,
-                &skia_safe::Paint::default()
-            );
-        }
+        draw_text(self.surface.canvas(), text, style, pos, line_width);
     }
 
     pub fn nine_patch(&mut self, path: &str, conf: NinePatch, pos: (i32, i32), size: (i32, i32)) {
+        let Some(image) = crate::image(path) else {
+            self.missing(pos, size);
+            return;
+        };
         let canvas = self.surface.canvas();
-        let image = crate::image(path);
         if image.width() < conf.left + conf.right || image.height() < conf.top + conf.bottom {
             info!("Nine patch image is too small!");
             return;
@@ -0,0 +1,156 @@
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use log::{error, info};
+use crate::{Event, Image, MrgrConfig, MrgrError};
+use crate::runtime::{Clock, dispatch, install_runtime, panic_message, release_runtime, runtime};
+use crate::runtime::{RuntimeEvent, RuntimeProxy, spawn_game_thread};
+
+const SUBMIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+// Only one runtime may exist per process, so `launch` fails until the previous one is finished or dropped.
+pub struct Headless {
+    viewport_size: (u32, u32),
+    dur_frame: Duration,
+    receiver: mpsc::Receiver<RuntimeEvent>,
+    game_thread: Option<JoinHandle<()>>,
+    outcome: Result<(), MrgrError>,
+    shutdown: bool,
+    frame: u64,
+}
+
+impl Headless {
+    pub fn launch(mut config: MrgrConfig) -> Result<Self, MrgrError> {
+        config.ensure_sanity()?;
+        let (sender, receiver) = mpsc::channel();
+        install_runtime(
+            &config, RuntimeProxy::Headless(sender), Clock::Virtual(Mutex::new(Duration::ZERO)))?;
+        let game_thread = Some(spawn_game_thread(config.take_game()));
+        let mut headless = Self {
+            viewport_size: config.viewport_size,
+            dur_frame: Duration::from_micros(1_000_000 / config.framerate as u64),
+            receiver,
+            game_thread,
+            outcome: Ok(()),
+            shutdown: false,
+            frame: 0,
+        };
+        headless.wait_for_game();
+        Ok(headless)
+    }
+
+    // Blocks until the game waits on the frame clock or for an event, or has finished, so the
+    // first `step` is not lost while the game thread is still starting up.
+    fn wait_for_game(&mut self) {
+        while !runtime().frames.is_synchronized() && !runtime().events.is_idle() {
+            if self.is_game_finished() { break; }
+            thread::sleep(SUBMIT_POLL_INTERVAL);
+        }
+    }
+
+    fn is_game_finished(&self) -> bool {
+        self.game_thread.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    pub fn send(&self, event: Event) {
+        dispatch(event);
+    }
+
+    pub fn step(&mut self) {
+        for event in self.receiver.try_iter() {
+            match event {
+                RuntimeEvent::ShutdownRequested => {
+                    self.shutdown = true;
+                    runtime().frames.shutdown();
+                }
+                RuntimeEvent::ZoomRequested(_) => {}
+            }
+        }
+        self.check_game_thread();
+        runtime().clock.advance(self.dur_frame);
+        runtime().frames.tick(runtime().clock.elapsed());
+        self.frame += 1;
+        while !runtime().frames.wait_submitted(SUBMIT_POLL_INTERVAL) {
+            if self.is_game_finished() { break; }
+            if runtime().events.is_idle() { break; }
+        }
+    }
+
+    pub fn step_frames(&mut self, frames: u64) {
+        for _ in 0..frames {
+            self.step();
+        }
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.shutdown && self.game_thread.is_some()
+    }
+
+    pub fn viewport_size(&self) -> (u32, u32) {
+        self.viewport_size
+    }
+
+    pub fn screenshot(&self) -> Result<Image, MrgrError> {
+        crate::screenshot()
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Result<[u8; 4], MrgrError> {
+        Ok(self.screenshot()?.pixel(x, y))
+    }
+
+    pub fn finish(mut self) -> Result<(), MrgrError> {
+        self.stop_game_thread();
+        std::mem::replace(&mut self.outcome, Ok(()))
+    }
+
+    fn stop_game_thread(&mut self) {
+        if let Some(jh) = self.game_thread.take() {
+            if !jh.is_finished() {
+                dispatch(Event::ExitRequested);
+            }
+            runtime().frames.shutdown();
+            Self::join(jh, &mut self.outcome);
+        }
+        release_runtime();
+    }
+
+    fn check_game_thread(&mut self) {
+        if self.game_thread.as_ref().is_some_and(|jh| jh.is_finished()) {
+            Self::join(self.game_thread.take().unwrap(), &mut self.outcome);
+        }
+    }
+
+    fn join(jh: JoinHandle<()>, outcome: &mut Result<(), MrgrError>) {
+        if let Err(payload) = jh.join() {
+            let message = panic_message(&*payload);
+            error!("Game thread panicked: {message}");
+            *outcome = Err(MrgrError::GamePanicked(message));
+        }
+    }
+}
+
+impl Drop for Headless {
+    fn drop(&mut self) {
+        self.stop_game_thread();
+    }
+}
+
+pub(crate) fn run_headless(config: MrgrConfig) -> Result<(), MrgrError> {
+    info!("Running headless, frames are driven by a virtual clock.");
+    let mut headless = Headless::launch(config)?;
+    while headless.is_running() {
+        headless.step();
+        // Nothing can wake a game blocked in `wait_event` without a window, so ask it to exit.
+        if runtime().events.is_idle() {
+            info!("Game is waiting for input that will never arrive, requesting exit.");
+            headless.send(Event::ExitRequested);
+        }
+        thread::sleep(headless.dur_frame);
+    }
+    headless.finish()
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use log::error;
+use crate::{Canvas, Color, Image, MrgrError, Palette};
+use crate::runtime::runtime;
+
+pub const MAXIMUM_PALETTE_SIZE: usize = 256;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PaletteCycle {
+    pub start: u8,
+    pub length: u8,
+    pub period: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IndexedImage {
+    width: u32,
+    height: u32,
+    indices: Vec<u8>,
+}
+
+impl IndexedImage {
+    pub fn new(width: u32, height: u32, indices: Vec<u8>) -> Self {
+        assert_eq!(indices.len(), width as usize * height as usize,
+                   "Index buffer does not match the image size!");
+        Self { width, height, indices }
+    }
+
+    // Pixels are matched against the palette; when a color repeats, the first index wins.
+    pub fn load(path: &str, palette: &Palette) -> Result<Self, MrgrError> {
+        let image = Image::load_png(path)?;
+        let lookup = lookup_table(palette.colors());
+        let indices = image.pixels().chunks_exact(4)
+            .map(|pixel| lookup.get(&Color::rgba(pixel[0], pixel[1], pixel[2], pixel[3])).copied())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| MrgrError::AssetDecoding { path: path.to_string() })?;
+        Ok(Self::new(image.width(), image.height(), indices))
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn index(&self, x: u32, y: u32) -> u8 {
+        self.indices[y as usize * self.width as usize + x as usize]
+    }
+}
+
+fn lookup_table(colors: &[Color]) -> HashMap<Color, u8> {
+    let mut lookup = HashMap::new();
+    for (index, color) in colors.iter().enumerate().rev() {
+        lookup.insert(*color, index as u8);
+    }
+    lookup
+}
+
+struct IndexPlane {
+    indices: Vec<u8>,
+    serial: u64,
+}
+
+struct Composition {
+    overlay_serial: u64,
+    plane_serial: u64,
+    palette: Vec<Color>,
+    image: skia_safe::Image,
+}
+
+// The indexed viewport is a `u8` plane resolved through the palette at present time, with the
+// RGBA viewport composited on top of it as an overlay for text, shapes and true-color images.
+pub(crate) struct IndexedState {
+    size: (u32, u32),
+    base: Vec<Color>,
+    back: Mutex<Vec<u8>>,
+    front: Mutex<IndexPlane>,
+    display: Mutex<Vec<Color>>,
+    cycles: Mutex<Vec<PaletteCycle>>,
+    composed: Mutex<Option<Composition>>,
+}
+
+impl IndexedState {
+    pub fn new(palette: &Palette, size: (u32, u32)) -> Self {
+        let indices = vec![0; size.0 as usize * size.1 as usize];
+        Self {
+            size,
+            base: palette.colors().to_vec(),
+            back: Mutex::new(indices.clone()),
+            front: Mutex::new(IndexPlane { indices, serial: 0 }),
+            display: Mutex::new(palette.colors().to_vec()),
+            cycles: Mutex::new(Vec::new()),
+            composed: Mutex::new(None),
+        }
+    }
+
+    pub fn current(&self, elapsed: Duration) -> Vec<Color> {
+        let display = self.display.lock().unwrap();
+        let mut current = display.clone();
+        for cycle in self.cycles.lock().unwrap().iter() {
+            let (start, length) = (cycle.start as usize, cycle.length as usize);
+            let shift = (elapsed.as_nanos() / cycle.period.as_nanos()) as usize % length;
+            for offset in 0..length {
+                current[start + (offset + shift) % length] = display[start + offset];
+            }
+        }
+        current
+    }
+
+    pub fn present(&self) {
+        let back = self.back.lock().unwrap();
+        let mut front = self.front.lock().unwrap();
+        front.indices.copy_from_slice(&back);
+        front.serial += 1;
+    }
+
+    pub fn front_serial(&self) -> u64 {
+        self.front.lock().unwrap().serial
+    }
+
+    pub fn compose(
+        &self,
+        overlay: &skia_safe::Image,
+        overlay_serial: u64,
+        palette: &[Color]
+    ) -> skia_safe::Image {
+        let front = self.front.lock().unwrap();
+        let mut composed = self.composed.lock().unwrap();
+        if let Some(composition) = composed.as_ref() {
+            if composition.overlay_serial == overlay_serial
+                && composition.plane_serial == front.serial
+                && composition.palette == palette {
+                return composition.image.clone();
+            }
+        }
+        let mut pixels = Vec::with_capacity(front.indices.len() * 4);
+        for &index in &front.indices {
+            let color = palette.get(index as usize).copied().unwrap_or(Color::TRANSPARENT);
+            pixels.extend_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+        let resolved = Image::new(self.size.0, self.size.1, pixels).to_skia();
+        let surface = skia_safe::surfaces::raster_n32_premul((self.size.0 as i32, self.size.1 as i32));
+        let (Some(resolved), Some(mut surface)) = (resolved, surface) else { return overlay.clone(); };
+        surface.canvas().draw_image(&resolved, (0.0, 0.0), None);
+        surface.canvas().draw_image(overlay, (0.0, 0.0), None);
+        let image = surface.image_snapshot();
+        *composed = Some(Composition {
+            overlay_serial,
+            plane_serial: front.serial,
+            palette: palette.to_vec(),
+            image: image.clone(),
+        });
+        image
+    }
+}
+
+fn indexed_state() -> Result<&'static IndexedState, MrgrError> {
+    runtime().indexed.as_ref()
+        .ok_or_else(|| MrgrError::invalid_config("indexed_palette", "indexed mode is not enabled"))
+}
+
+pub(crate) fn compose_viewport(overlay: skia_safe::Image, overlay_serial: u64) -> skia_safe::Image {
+    let Some(indexed) = runtime().indexed.as_ref() else { return overlay; };
+    indexed.compose(&overlay, overlay_serial, &indexed.current(runtime().clock.elapsed()))
+}
+
+pub fn set_palette(palette: &Palette) -> Result<(), MrgrError> {
+    let indexed = indexed_state()?;
+    if palette.len() != indexed.base.len() {
+        return Err(MrgrError::invalid_config("indexed_palette", format!(
+            "expected {} colors, got {}", indexed.base.len(), palette.len())));
+    }
+    *indexed.display.lock().unwrap() = palette.colors().to_vec();
+    Ok(())
+}
+
+pub fn set_palette_color(index: u8, color: impl Into<Color>) -> Result<(), MrgrError> {
+    let indexed = indexed_state()?;
+    let mut display = indexed.display.lock().unwrap();
+    let Some(entry) = display.get_mut(index as usize) else {
+        return Err(MrgrError::invalid_config("indexed_palette", format!(
+            "index {index} is outside the palette")));
+    };
+    *entry = color.into();
+    Ok(())
+}
+
+pub fn reset_palette() -> Result<(), MrgrError> {
+    let indexed = indexed_state()?;
+    *indexed.display.lock().unwrap() = indexed.base.clone();
+    indexed.cycles.lock().unwrap().clear();
+    Ok(())
+}
+
+pub fn add_palette_cycle(cycle: PaletteCycle) -> Result<(), MrgrError> {
+    let indexed = indexed_state()?;
+    if cycle.length == 0 || cycle.start as usize + cycle.length as usize > indexed.base.len() {
+        return Err(MrgrError::invalid_config("palette_cycle", format!(
+            "{}..{} is outside the palette", cycle.start, cycle.start as usize + cycle.length as usize)));
+    }
+    if cycle.period.is_zero() {
+        return Err(MrgrError::invalid_config("palette_cycle", "the period must not be zero"));
+    }
+    indexed.cycles.lock().unwrap().push(cycle);
+    Ok(())
+}
+
+pub fn clear_palette_cycles() -> Result<(), MrgrError> {
+    indexed_state()?.cycles.lock().unwrap().clear();
+    Ok(())
+}
+
+impl Canvas {
+    pub fn clear_indexed(&mut self, index: u8) {
+        let Some(indexed) = runtime().indexed.as_ref() else {
+            error!("Cannot clear the index plane: indexed mode is not enabled");
+            return;
+        };
+        indexed.back.lock().unwrap().fill(index);
+        self.surface.canvas().clear(Color::TRANSPARENT.to_skia());
+    }
+
+    // Indices whose base palette color is fully transparent are skipped, after remapping.
+    pub fn indexed_image(&mut self, image: &IndexedImage, pos: (i32, i32), remap: Option<&[u8]>) {
+        let Some(indexed) = runtime().indexed.as_ref() else {
+            error!("Cannot draw an indexed image: indexed mode is not enabled");
+            return;
+        };
+        let (width, height) = (indexed.size.0 as i32, indexed.size.1 as i32);
+        let mut plane = indexed.back.lock().unwrap();
+        for y in 0..image.height as i32 {
+            let dst_y = pos.1 + y;
+            if dst_y < 0 || dst_y >= height { continue; }
+            for x in 0..image.width as i32 {
+                let dst_x = pos.0 + x;
+                if dst_x < 0 || dst_x >= width { continue; }
+                let index = image.index(x as u32, y as u32);
+                let index = remap.and_then(|remap| remap.get(index as usize)).copied().unwrap_or(index);
+                if indexed.base.get(index as usize).is_none_or(|color| color.a == 0) { continue; }
+                plane[dst_y as usize * width as usize + dst_x as usize] = index;
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use crate::{Axis, Event, Key, Mouse};
+use crate::runtime::runtime;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputState {
+    keys_down: BTreeSet<Key>,
+    keys_pressed: BTreeSet<Key>,
+    keys_released: BTreeSet<Key>,
+    mouse_down: BTreeSet<Mouse>,
+    mouse_pressed: BTreeSet<Mouse>,
+    mouse_released: BTreeSet<Mouse>,
+    axes: BTreeMap<Axis, f32>,
+    mouse_position: (u32, u32),
+    wheel: (f32, f32),
+}
+
+impl InputState {
+    pub fn is_down(&self, key: Key) -> bool {
+        self.keys_down.contains(&key)
+    }
+
+    pub fn just_pressed(&self, key: Key) -> bool {
+        self.keys_pressed.contains(&key)
+    }
+
+    pub fn just_released(&self, key: Key) -> bool {
+        self.keys_released.contains(&key)
+    }
+
+    pub fn is_mouse_down(&self, mouse: Mouse) -> bool {
+        self.mouse_down.contains(&mouse)
+    }
+
+    pub fn mouse_just_pressed(&self, mouse: Mouse) -> bool {
+        self.mouse_pressed.contains(&mouse)
+    }
+
+    pub fn mouse_just_released(&self, mouse: Mouse) -> bool {
+        self.mouse_released.contains(&mouse)
+    }
+
+    pub fn axis(&self, axis: Axis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    pub fn mouse_position(&self) -> (u32, u32) {
+        self.mouse_position
+    }
+
+    pub fn wheel(&self) -> (f32, f32) {
+        self.wheel
+    }
+
+    fn apply(&mut self, event: &Event) {
+        match *event {
+            Event::KeyPressed(key) => {
+                if self.keys_down.insert(key) {
+                    self.keys_pressed.insert(key);
+                }
+            }
+            Event::KeyReleased(key) => {
+                if self.keys_down.remove(&key) {
+                    self.keys_released.insert(key);
+                }
+            }
+            Event::MousePressed(mouse) => {
+                if self.mouse_down.insert(mouse) {
+                    self.mouse_pressed.insert(mouse);
+                }
+            }
+            Event::MouseReleased(mouse) => {
+                if self.mouse_down.remove(&mouse) {
+                    self.mouse_released.insert(mouse);
+                }
+            }
+            Event::MouseMoved(x, y) => self.mouse_position = (x, y),
+            Event::MouseWheel(x, y) => self.wheel = (self.wheel.0 + x, self.wheel.1 + y),
+            Event::AxisChanged(axis, value) => {
+                self.axes.insert(axis, value);
+            }
+            _ => {}
+        }
+    }
+
+    fn clear_transient(&mut self) {
+        self.keys_pressed.clear();
+        self.keys_released.clear();
+        self.mouse_pressed.clear();
+        self.mouse_released.clear();
+        self.wheel = (0.0, 0.0);
+    }
+}
+
+pub(crate) struct InputTracker {
+    pending: Mutex<InputState>,
+    latched: Mutex<InputState>,
+}
+
+impl InputTracker {
+    pub fn new() -> Self {
+        Self {
+            pending: Mutex::new(InputState::default()),
+            latched: Mutex::new(InputState::default()),
+        }
+    }
+
+    pub fn apply(&self, event: &Event) {
+        self.pending.lock().unwrap().apply(event);
+    }
+
+    pub fn latch(&self) {
+        let mut pending = self.pending.lock().unwrap();
+        *self.latched.lock().unwrap() = pending.clone();
+        pending.clear_transient();
+    }
+
+    pub fn snapshot(&self) -> InputState {
+        self.latched.lock().unwrap().clone()
+    }
+}
+
+pub fn input() -> InputState {
+    runtime().input.snapshot()
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use winit::keyboard::KeyCode;
+use crate::Key;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "Vec<(KeyCode, Key)>", into = "Vec<(KeyCode, Key)>")]
+pub struct Keymap {
+    bindings: HashMap<KeyCode, Key>,
+}
+
+impl Keymap {
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    pub fn bind(&mut self, code: KeyCode, key: Key) -> &mut Self {
+        self.bindings.insert(code, key);
+        self
+    }
+
+    pub fn unbind(&mut self, code: KeyCode) -> &mut Self {
+        self.bindings.remove(&code);
+        self
+    }
+
+    pub fn unbind_key(&mut self, key: Key) -> &mut Self {
+        self.bindings.retain(|_, bound| *bound != key);
+        self
+    }
+
+    pub fn lookup(&self, code: KeyCode) -> Option<Key> {
+        self.bindings.get(&code).copied()
+    }
+
+    pub fn codes_for(&self, key: Key) -> Vec<KeyCode> {
+        self.bindings.iter()
+            .filter(|(_, bound)| **bound == key)
+            .map(|(code, _)| *code)
+            .collect()
+    }
+}
+
+impl From<Vec<(KeyCode, Key)>> for Keymap {
+    fn from(value: Vec<(KeyCode, Key)>) -> Self {
+        Self {
+            bindings: value.into_iter().collect(),
+        }
+    }
+}
+
+impl From<Keymap> for Vec<(KeyCode, Key)> {
+    fn from(value: Keymap) -> Self {
+        let mut bindings: Vec<_> = value.bindings.into_iter().collect();
+        bindings.sort_by_key(|(_, key)| *key);
+        bindings
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut keymap = Self::empty();
+        keymap
+            .bind(KeyCode::ArrowUp, Key::Up)
+            .bind(KeyCode::ArrowDown, Key::Down)
+            .bind(KeyCode::ArrowLeft, Key::Left)
+            .bind(KeyCode::ArrowRight, Key::Right)
+            .bind(KeyCode::KeyS, Key::North)
+            .bind(KeyCode::KeyZ, Key::South)
+            .bind(KeyCode::KeyA, Key::West)
+            .bind(KeyCode::KeyX, Key::East)
+            .bind(KeyCode::Backspace, Key::Select)
+            .bind(KeyCode::Enter, Key::Start)
+            .bind(KeyCode::KeyQ, Key::LeftBumper)
+            .bind(KeyCode::KeyW, Key::RightBumper)
+            .bind(KeyCode::Digit1, Key::LeftTrigger)
+            .bind(KeyCode::Digit2, Key::RightTrigger)
+            .bind(KeyCode::KeyC, Key::LeftThumb)
+            .bind(KeyCode::KeyV, Key::RightThumb);
+        keymap
+    }
+}
@@ -1,23 +1,73 @@
 mod config;
+mod overrides;
+mod settings;
+mod save;
 mod graphics;
+mod shapes;
+mod color;
+mod indexed;
 mod runtime;
 mod event;
 mod resource;
 mod common;
+mod error;
+mod headless;
+mod screenshot;
+mod frame;
+mod game;
+mod stats;
+mod display;
+mod filter;
+mod keymap;
+mod input;
+#[cfg(feature = "gamepad")]
+mod gamepad;
 
 use log::{error, info};
 
 pub use crate::config::MrgrConfig;
+pub use crate::overrides::CONFIG_FILE_NAME;
+pub use crate::save::{SaveMetadata, SaveSlot, SaveStore};
+pub use crate::settings::{settings, Settings, SETTINGS_FILE_NAME, SETTINGS_SCHEMA_VERSION};
+pub use crate::display::{FullscreenMode, maximum_zoom_level, ScalingPolicy, set_zoom_level, zoom_level};
+pub use crate::error::MrgrError;
+pub use crate::filter::{set_upscale_filter, upscale_filter, UpscaleFilter};
 pub use crate::graphics::*;
+pub use crate::shapes::ShapeStyle;
+pub use crate::color::{Color, Palette};
+pub use crate::indexed::{add_palette_cycle, clear_palette_cycles, IndexedImage, PaletteCycle};
+pub use crate::indexed::{reset_palette, set_palette, set_palette_color, MAXIMUM_PALETTE_SIZE};
 pub use crate::event::*;
 pub use crate::resource::*;
+pub use crate::keymap::Keymap;
+pub use crate::input::{input, InputState};
+pub use winit::keyboard::KeyCode;
+pub use crate::runtime::{elapsed, exit};
+pub use crate::headless::Headless;
+pub use crate::screenshot::*;
+pub use crate::stats::{frame_stats, FrameStats};
+pub use crate::game::{Game, GameFn};
+pub use crate::frame::{frame_count, next_frame, run_fixed_loop, run_loop, submit_frame, update_step};
 
-pub fn mrgr_launch(config: MrgrConfig) {
-    env_logger::init();
+pub fn mrgr_launch(mut config: MrgrConfig) -> Result<(), MrgrError> {
+    let _ = env_logger::try_init();
     info!("Midnight233's Retro Game Renderer, Version {}", env!("CARGO_PKG_VERSION"));
-    if !config.ensure_sanity() {
-        error!("Configuration is not sane, aborting!");
-        return;
+    if let Some(mut settings) = config.settings.take() {
+        settings.apply_window_settings(&mut config);
+        settings::install_settings(settings);
     }
-    runtime::launch_softbuffer_skia_raster_runtime(config);
+    if let Err(err) = config.ensure_sanity() {
+        error!("Configuration is not sane, aborting: {err}");
+        return Err(err);
+    }
+    let result = if config.headless {
+        headless::run_headless(config)
+    } else {
+        runtime::launch_softbuffer_skia_raster_runtime(config)
+    };
+    resource::report_missing_assets();
+    if let Err(err) = &result {
+        error!("Runtime terminated with an error: {err}");
+    }
+    result
 }
\ No newline at end of file
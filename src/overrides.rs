@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+use log::warn;
+use serde::Deserialize;
+use crate::{FullscreenMode, MrgrConfig, MrgrError, ScalingPolicy};
+
+pub const CONFIG_FILE_NAME: &str = "mrgr.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    zoom_level: Option<u32>,
+    framerate: Option<u32>,
+    update_rate: Option<u32>,
+    caption: Option<String>,
+    fullscreen: Option<FullscreenMode>,
+    scaling: Option<ScalingPolicy>,
+    headless: Option<bool>,
+    crash_screen: Option<bool>,
+    gamepad_dead_zone: Option<f32>,
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    Some(env::current_exe().ok()?.parent()?.join(CONFIG_FILE_NAME))
+}
+
+fn parse_number(field: &'static str, value: &str) -> Result<u32, MrgrError> {
+    value.parse()
+        .map_err(|_| MrgrError::invalid_config(field, format!("`{value}` is not a valid number")))
+}
+
+fn parse_fullscreen(value: &str) -> Result<FullscreenMode, MrgrError> {
+    match value {
+        "windowed" => Ok(FullscreenMode::Windowed),
+        "borderless" => Ok(FullscreenMode::Borderless),
+        "exclusive" => Ok(FullscreenMode::Exclusive),
+        _ => Err(MrgrError::invalid_config("fullscreen", format!(
+            "`{value}` is not one of windowed, borderless or exclusive"))),
+    }
+}
+
+impl MrgrConfig {
+    // Logging is initialized here so warnings about the overrides are not lost before `mrgr_launch`.
+    // Persisted settings apply first, then the file, then the arguments; the result is validated once.
+    pub fn with_overrides(mut self) -> Result<Self, MrgrError> {
+        let _ = env_logger::try_init();
+        if let Some(mut settings) = self.settings.take() {
+            settings.apply_window_settings(&mut self);
+            self.settings = Some(settings);
+        }
+        if let Some(path) = default_config_path().filter(|path| path.is_file()) {
+            self.apply_file(path)?;
+        }
+        self.apply_args(env::args().skip(1))?;
+        self.ensure_sanity()?;
+        Ok(self)
+    }
+
+    pub fn apply_file(&mut self, path: impl AsRef<Path>) -> Result<(), MrgrError> {
+        let path = path.as_ref().display().to_string();
+        let text = fs::read_to_string(&path)
+            .map_err(|source| MrgrError::AssetIo { path: path.clone(), source })?;
+        let file: ConfigFile = toml::from_str(&text)
+            .map_err(|err| MrgrError::ConfigSyntax { path, message: err.message().to_string() })?;
+        if let Some(zoom_level) = file.zoom_level { self.zoom_level = zoom_level; }
+        if let Some(framerate) = file.framerate { self.framerate = framerate; }
+        if let Some(update_rate) = file.update_rate { self.update_rate = Some(update_rate); }
+        if let Some(caption) = file.caption { self.caption = caption.into(); }
+        if let Some(fullscreen) = file.fullscreen { self.fullscreen = fullscreen; }
+        if let Some(scaling) = file.scaling { self.scaling = scaling; }
+        if let Some(headless) = file.headless { self.headless = headless; }
+        if let Some(crash_screen) = file.crash_screen { self.crash_screen = crash_screen; }
+        if let Some(dead_zone) = file.gamepad_dead_zone { self.gamepad_dead_zone = dead_zone; }
+        Ok(())
+    }
+
+    pub fn apply_args<I: IntoIterator<Item = String>>(&mut self, args: I) -> Result<(), MrgrError> {
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let mut value = |field: &'static str| inline.clone().or_else(|| args.next())
+                .ok_or_else(|| MrgrError::invalid_config(field, format!("missing value for `{name}`")));
+            match name.as_str() {
+                "--zoom" => self.zoom_level = parse_number("zoom_level", &value("zoom_level")?)?,
+                "--fps" => self.framerate = parse_number("framerate", &value("framerate")?)?,
+                "--fullscreen" => {
+                    let mode = inline.or_else(|| args.next_if(|next| parse_fullscreen(next).is_ok()));
+                    self.fullscreen = match mode {
+                        Some(mode) => parse_fullscreen(&mode)?,
+                        None => FullscreenMode::Borderless,
+                    };
+                }
+                "--windowed" => self.fullscreen = FullscreenMode::Windowed,
+                "--headless" => self.headless = true,
+                _ => warn!("Ignoring unrecognized command-line argument `{name}`."),
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
+use std::sync::{Arc, Mutex};
+use log::{error, warn};
 use once_cell::sync::Lazy;
-use crate::TextStyle;
+use crate::{Color, MrgrError, TextStyle};
 
 pub(crate) struct BitmapFont {
     columns: i32,
@@ -10,10 +12,22 @@ pub(crate) struct BitmapFont {
     pub bitmaps: BTreeMap<TextStyle, skia_safe::Image>,
 }
 
-fn load_bitmap_image(path_base: &str, variant: &str) -> skia_safe::Image {
-    let data = fs::read(format!("{path_base}-{variant}.png")).unwrap();
-    let data = skia_safe::Data::new_copy(&data);
-    skia_safe::Image::from_encoded(data).unwrap()
+fn read_asset(path: &str) -> Result<Vec<u8>, MrgrError> {
+    fs::read(path).map_err(|source| MrgrError::AssetIo { path: path.to_string(), source })
+}
+
+pub(crate) fn read_asset_to_string(path: &str) -> Result<String, MrgrError> {
+    fs::read_to_string(path).map_err(|source| MrgrError::AssetIo { path: path.to_string(), source })
+}
+
+pub(crate) fn decode_image(path: &str) -> Result<skia_safe::Image, MrgrError> {
+    let data = skia_safe::Data::new_copy(&read_asset(path)?);
+    skia_safe::Image::from_encoded(data)
+        .ok_or_else(|| MrgrError::AssetDecoding { path: path.to_string() })
+}
+
+fn load_bitmap_image(path_base: &str, variant: &str) -> Result<skia_safe::Image, MrgrError> {
+    decode_image(&format!("{path_base}-{variant}.png"))
 }
 
 pub(crate) struct BitmapFontLookupResult {
@@ -22,10 +36,10 @@ pub(crate) struct BitmapFontLookupResult {
 }
 
 impl BitmapFont {
-    pub fn load(path_base: &str) -> Option<Self> {
-        let glyphs_full_width = fs::read_to_string(format!("{path_base}-gf.txt")).ok()?
+    pub fn load(path_base: &str) -> Result<Self, MrgrError> {
+        let glyphs_full_width = read_asset_to_string(&format!("{path_base}-gf.txt"))?
             .chars().collect::<Vec<_>>();
-        let glyphs_half_width = fs::read_to_string(format!("{path_base}-gh.txt")).ok()?
+        let glyphs_half_width = read_asset_to_string(&format!("{path_base}-gh.txt"))?
             .chars().collect::<Vec<_>>();
         let hw_begin = glyphs_full_width.len();
         let glyphs_full_width = glyphs_full_width.into_iter()
@@ -38,17 +52,17 @@ impl BitmapFont {
             .collect::<BTreeMap<_, _>>();
         let width_small = 16;
         let bitmaps = [
-            (TextStyle::SmallNormal, load_bitmap_image(path_base, "n")),
-            (TextStyle::SmallBold, load_bitmap_image(path_base, "nb")),
-            (TextStyle::SmallItalic, load_bitmap_image(path_base, "ni")),
-            (TextStyle::SmallBoldItalic, load_bitmap_image(path_base, "nbi")),
-            (TextStyle::LargeNormal, load_bitmap_image(path_base, "l")),
-            (TextStyle::LargeBold, load_bitmap_image(path_base, "lb")),
-            (TextStyle::LargeItalic, load_bitmap_image(path_base, "li")),
-            (TextStyle::LargeBoldItalic, load_bitmap_image(path_base, "lbi")),
+            (TextStyle::SmallNormal, load_bitmap_image(path_base, "n")?),
+            (TextStyle::SmallBold, load_bitmap_image(path_base, "nb")?),
+            (TextStyle::SmallItalic, load_bitmap_image(path_base, "ni")?),
+            (TextStyle::SmallBoldItalic, load_bitmap_image(path_base, "nbi")?),
+            (TextStyle::LargeNormal, load_bitmap_image(path_base, "l")?),
+            (TextStyle::LargeBold, load_bitmap_image(path_base, "lb")?),
+            (TextStyle::LargeItalic, load_bitmap_image(path_base, "li")?),
+            (TextStyle::LargeBoldItalic, load_bitmap_image(path_base, "lbi")?),
         ].iter().cloned().collect::<BTreeMap<_, _>>();
         let columns = bitmaps[&TextStyle::SmallNormal].width() / width_small;
-        Some(Self {
+        Ok(Self {
             columns,
             glyphs_full_width,
             glyphs_half_width,
@@ -79,38 +93,126 @@ impl BitmapFont {
     }
 }
 
-static mut FONT: Option<BitmapFont> = None;
+// Read by the event loop for the stats overlay while the game thread may be loading a new font.
+static FONT: Lazy<Mutex<Option<Arc<BitmapFont>>>> = Lazy::new(|| Mutex::new(None));
 
-pub(crate) fn font() -> &'static BitmapFont {
-    unsafe { FONT.as_ref().unwrap() }
+pub(crate) fn font() -> Option<Arc<BitmapFont>> {
+    FONT.lock().unwrap().clone()
 }
 
 pub fn is_font_loaded() -> bool {
-    unsafe { FONT.is_some() }
+    FONT.lock().unwrap().is_some()
+}
+
+pub fn try_load_bitmap_font(path_base: &str) -> Result<(), MrgrError> {
+    let font = BitmapFont::load(path_base)?;
+    *FONT.lock().unwrap() = Some(Arc::new(font));
+    Ok(())
 }
 
 pub fn load_bitmap_font(path_base: &str) {
-    unsafe { FONT = BitmapFont::load(path_base); }
+    if let Err(err) = try_load_bitmap_font(path_base) {
+        error!("Failed to load bitmap font: {err}");
+        *FONT.lock().unwrap() = None;
+    }
 }
 
 pub(crate) static mut PATH_IMAGE_POOL: Lazy<HashMap<String, skia_safe::Image>> = Lazy::new(|| HashMap::new());
 
-pub fn load_image(path: &str) {
-    let data = fs::read(path).unwrap();
-    let data = skia_safe::Data::new_copy(&data);
-    let image = skia_safe::Image::from_encoded(data).unwrap();
+pub fn try_load_image(path: &str) -> Result<(), MrgrError> {
+    let image = decode_image(path)?;
     unsafe { PATH_IMAGE_POOL.insert(path.to_string(), image); }
+    Ok(())
+}
+
+pub fn load_image(path: &str) {
+    try_load_image(path).unwrap_or_else(|err| panic!("{err}"));
 }
 
 pub fn unload_image(path: &str) {
     unsafe { PATH_IMAGE_POOL.remove(path); }
 }
 
-pub(crate) fn image(path: &str) -> &'static skia_safe::Image {
+pub(crate) fn image(path: &str) -> Option<&'static skia_safe::Image> {
     if let Some(image) = unsafe { PATH_IMAGE_POOL.get(path) } {
-        image
-    } else {
-        load_image(path);
-        unsafe { PATH_IMAGE_POOL.get(path).unwrap() }
+        return Some(image);
+    }
+    if MISSING_ASSETS.lock().unwrap().contains(path) {
+        return None;
+    }
+    match try_load_image(path) {
+        Ok(()) => unsafe { PATH_IMAGE_POOL.get(path) },
+        Err(err) => {
+            warn!("Drawing placeholder for missing asset: {err}");
+            MISSING_ASSETS.lock().unwrap().insert(path.to_string());
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MissingTexture {
+    Checkerboard { cell_size: i32, colors: (Color, Color) },
+    Image(String),
+}
+
+impl Default for MissingTexture {
+    fn default() -> Self {
+        Self::Checkerboard {
+            cell_size: 4,
+            colors: (Color::MAGENTA, Color::BLACK),
+        }
+    }
+}
+
+impl MissingTexture {
+    fn render(&self) -> skia_safe::Image {
+        match self {
+            Self::Checkerboard { cell_size, colors } => {
+                let cell_size = (*cell_size).max(1);
+                let mut surface = skia_safe::surfaces::raster_n32_premul(
+                    (cell_size * 2, cell_size * 2)).unwrap();
+                let canvas = surface.canvas();
+                canvas.clear(colors.0.to_skia());
+                let mut paint = skia_safe::Paint::default();
+                paint.set_anti_alias(false);
+                paint.set_color(colors.1.to_skia());
+                let size = cell_size as f32;
+                canvas.draw_rect(skia_safe::Rect::from_xywh(size, 0.0, size, size), &paint);
+                canvas.draw_rect(skia_safe::Rect::from_xywh(0.0, size, size, size), &paint);
+                surface.image_snapshot()
+            }
+            Self::Image(path) => decode_image(path).unwrap_or_else(|err| {
+                error!("Failed to load the missing texture placeholder: {err}");
+                Self::default().render()
+            }),
+        }
+    }
+}
+
+static MISSING_TEXTURE: Lazy<Mutex<Option<skia_safe::Image>>> = Lazy::new(|| Mutex::new(None));
+
+static MISSING_ASSETS: Lazy<Mutex<BTreeSet<String>>> = Lazy::new(|| Mutex::new(BTreeSet::new()));
+
+pub fn set_missing_texture(texture: &MissingTexture) {
+    *MISSING_TEXTURE.lock().unwrap() = Some(texture.render());
+}
+
+pub(crate) fn missing_texture() -> skia_safe::Image {
+    MISSING_TEXTURE.lock().unwrap()
+        .get_or_insert_with(|| MissingTexture::default().render())
+        .clone()
+}
+
+pub fn missing_assets() -> Vec<String> {
+    MISSING_ASSETS.lock().unwrap().iter().cloned().collect()
+}
+
+pub(crate) fn report_missing_assets() {
+    let missing = missing_assets();
+    if missing.is_empty() { return; }
+    warn!("{} asset(s) were missing during this session:", missing.len());
+    for path in missing {
+        warn!("  {path}");
     }
 }
\ No newline at end of file
@@ -1,60 +1,272 @@
 use std::{slice, thread};
-use std::sync::{Arc, Mutex};
-use std::thread::ThreadId;
+use std::any::Any;
+use std::sync::{Arc, mpsc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{JoinHandle, ThreadId};
 use std::time::{Duration, Instant};
-use winit::event::{DeviceEvent, Event, StartCause, WindowEvent};
+use log::{error, info, warn};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::{DeviceEvent, ElementState, Event, MouseButton, MouseScrollDelta, StartCause, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopProxy};
+use winit::keyboard::{KeyCode, ModifiersState, PhysicalKey};
 use winit::window::{Window, WindowBuilder, WindowButtons};
-use crate::{config, MrgrConfig};
+use crate::{config, Color, FullscreenMode, Game, MrgrConfig, MrgrError, PresentMode, TextStyle};
+use crate::display::DisplayState;
+use crate::event::EventQueue;
+use crate::filter::{draw_upscaled, FilterCache, FilterState};
+use crate::indexed::IndexedState;
+use crate::frame::FrameClock;
+use crate::stats::{draw_overlay, StatsTracker};
+use crate::input::InputTracker;
+use crate::{Event as MrgrEvent, Mouse};
 
 static mut GAME_THREAD: Option<ThreadId> = None;
 
+pub struct FrontBuffer {
+    pub image: skia_safe::Image,
+    pub serial: u64,
+}
+
 pub struct Runtime {
-    pub proxy: EventLoopProxy<RuntimeEvent>,
+    pub proxy: RuntimeProxy,
     pub surface: Arc<Mutex<skia_safe::Surface>>,
+    pub front: Mutex<FrontBuffer>,
+    pub present_mode: PresentMode,
+    pub events: EventQueue,
+    pub input: InputTracker,
+    pub clock: Clock,
+    pub frames: FrameClock,
+    pub update_step: Duration,
+    pub stats: StatsTracker,
+    pub display: DisplayState,
+    pub filter: FilterState,
+    pub indexed: Option<IndexedState>,
 }
 
 static mut RT: Option<Runtime> = None;
 
+// Set while a runtime is installed, so a second launch cannot replace `RT` under a live game thread.
+static RUNTIME_LIVE: AtomicBool = AtomicBool::new(false);
+
 pub fn runtime() -> &'static Runtime {
     unsafe { RT.as_ref().unwrap() }
 }
 
 pub enum RuntimeEvent {
-    ShutdownRequested
+    ShutdownRequested,
+    ZoomRequested(u32),
+}
+
+pub enum RuntimeProxy {
+    Window(EventLoopProxy<RuntimeEvent>),
+    Headless(mpsc::Sender<RuntimeEvent>),
+}
+
+impl RuntimeProxy {
+    pub fn send(&self, event: RuntimeEvent) {
+        match self {
+            Self::Window(proxy) => { let _ = proxy.send_event(event); }
+            Self::Headless(sender) => { let _ = sender.send(event); }
+        }
+    }
+}
+
+pub enum Clock {
+    Real(Instant),
+    Virtual(Mutex<Duration>),
+}
+
+impl Clock {
+    pub fn elapsed(&self) -> Duration {
+        match self {
+            Self::Real(start) => start.elapsed(),
+            Self::Virtual(now) => *now.lock().unwrap(),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        if let Self::Virtual(now) = self {
+            *now.lock().unwrap() += by;
+        }
+    }
+}
+
+pub fn elapsed() -> Duration {
+    runtime().clock.elapsed()
+}
+
+const WHEEL_PIXELS_PER_LINE: f64 = 16.0;
+
+fn viewport_position(
+    config: &MrgrConfig,
+    present_bounds: &skia_safe::Rect,
+    position: PhysicalPosition<f64>
+) -> (u32, u32) {
+    let (v_width, v_height) = config.viewport_size;
+    let scale_x = present_bounds.width() as f64 / v_width as f64;
+    let scale_y = present_bounds.height() as f64 / v_height as f64;
+    let x = ((position.x - present_bounds.left as f64) / scale_x).floor();
+    let y = ((position.y - present_bounds.top as f64) / scale_y).floor();
+    (x.clamp(0.0, (v_width - 1) as f64) as u32, y.clamp(0.0, (v_height - 1) as f64) as u32)
+}
+
+fn mouse_button(button: MouseButton) -> Option<Mouse> {
+    match button {
+        MouseButton::Left => Some(Mouse::Main),
+        MouseButton::Right => Some(Mouse::Secondary),
+        MouseButton::Middle => Some(Mouse::Tertiary),
+        _ => None,
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<unknown panic payload>".to_string()
+    }
+}
+
+fn draw_crash_screen(config: &MrgrConfig, message: &str) {
+    let line_width = config.viewport_size.0 as i32 - 16;
+    crate::paint(|mut canvas| {
+        canvas.clear(Color::NAVY);
+        canvas.text("The game thread has crashed!", TextStyle::SmallBold, (8, 8), line_width);
+        canvas.text(message, TextStyle::SmallNormal, (8, 32), line_width);
+    });
+    crate::present();
+}
+
+pub fn exit() {
+    runtime().proxy.send(RuntimeEvent::ShutdownRequested);
+}
+
+pub(crate) fn install_runtime(
+    config: &MrgrConfig,
+    proxy: RuntimeProxy,
+    clock: Clock
+) -> Result<Arc<Mutex<skia_safe::Surface>>, MrgrError> {
+    if RUNTIME_LIVE.swap(true, Ordering::SeqCst) {
+        return Err(MrgrError::RuntimeActive);
+    }
+    let viewport_surface = match skia_raster_surface(config) {
+        Ok(surface) => Arc::new(Mutex::new(surface)),
+        Err(err) => {
+            release_runtime();
+            return Err(err);
+        }
+    };
+    let image = {
+        let mut surface = viewport_surface.lock().unwrap();
+        let background = if config.indexed_palette.is_some() { Color::TRANSPARENT } else { Color::WHITE };
+        surface.canvas().clear(background.to_skia());
+        surface.image_snapshot()
+    };
+    crate::set_missing_texture(&config.missing_texture);
+
+    let rt = Runtime {
+        proxy,
+        surface: viewport_surface.clone(),
+        front: Mutex::new(FrontBuffer { image, serial: 0 }),
+        present_mode: config.present_mode,
+        events: EventQueue::new(),
+        input: InputTracker::new(),
+        clock,
+        frames: FrameClock::new(),
+        update_step: Duration::from_micros(
+            1_000_000 / config.update_rate.unwrap_or(config.framerate) as u64),
+        stats: StatsTracker::new(),
+        display: DisplayState::new(config.zoom_level),
+        filter: FilterState::new(config.upscale_filter),
+        indexed: config.indexed_palette.as_ref()
+            .map(|palette| IndexedState::new(palette, config.viewport_size)),
+    };
+    unsafe { RT = Some(rt) }
+    Ok(viewport_surface)
+}
+
+// Only call this once the game thread has been joined.
+pub(crate) fn release_runtime() {
+    RUNTIME_LIVE.store(false, Ordering::SeqCst);
 }
 
-fn winit_create_window(el: &EventLoop<RuntimeEvent>, config: &MrgrConfig) -> Window {
-    WindowBuilder::new()
+pub(crate) fn spawn_game_thread(mut game: Box<dyn Game>) -> JoinHandle<()> {
+    let jh = thread::spawn(move || {
+        thread::park();
+        game.run();
+        exit();
+    });
+    unsafe { GAME_THREAD = Some(jh.thread().id()); }
+    jh.thread().unpark();
+    jh
+}
+
+pub(crate) fn dispatch(event: MrgrEvent) {
+    runtime().input.apply(&event);
+    runtime().events.push(event);
+}
+
+fn present_bounds(config: &MrgrConfig, size: PhysicalSize<u32>) -> skia_safe::Rect {
+    let zoom_range = (config::MINIMUM_ZOOM_LEVEL, config::MAXIMUM_ZOOM_LEVEL);
+    config.scaling.bounds(config.viewport_size, zoom_range, size)
+}
+
+fn zoomed_size(config: &MrgrConfig, zoom_level: u32) -> PhysicalSize<u32> {
+    PhysicalSize::new(config.viewport_size.0 * zoom_level, config.viewport_size.1 * zoom_level)
+}
+
+fn toggle_fullscreen(config: &MrgrConfig, window: &Window) -> Option<PhysicalSize<u32>> {
+    if window.fullscreen().is_some() {
+        window.set_fullscreen(None);
+        return window.request_inner_size(zoomed_size(config, runtime().display.zoom_level()));
+    } else if config.fullscreen == FullscreenMode::Windowed {
+        window.set_fullscreen(FullscreenMode::Borderless.to_winit(window));
+    } else {
+        window.set_fullscreen(config.fullscreen.to_winit(window));
+    }
+    None
+}
+
+fn winit_create_window(el: &EventLoop<RuntimeEvent>, config: &MrgrConfig) -> Result<Window, MrgrError> {
+    Ok(WindowBuilder::new()
         .with_active(true)
         .with_inner_size(config.physical_size())
-        .with_min_inner_size(config::minimum_physical_size())
+        .with_min_inner_size(config.minimum_physical_size())
         .with_max_inner_size(config::maximum_physical_size())
-        .with_resizable(false)
+        .with_resizable(true)
         .with_title(config.caption.clone())
-        .with_enabled_buttons(WindowButtons::CLOSE)
-        .build(&el).unwrap()
+        .with_enabled_buttons(WindowButtons::all())
+        .build(el)?)
+}
+
+fn softbuffer_resize(
+    surface: &mut softbuffer::Surface<&Window, &Window>,
+    size: PhysicalSize<u32>
+) -> Result<(), MrgrError> {
+    let (Ok(width), Ok(height)) = (size.width.try_into(), size.height.try_into()) else {
+        return Err(MrgrError::SurfaceCreation("softbuffer"));
+    };
+    surface.resize(width, height)?;
+    Ok(())
 }
 
 fn softbuffer_create_context<'a>(
-    config: &MrgrConfig,
+    size: PhysicalSize<u32>,
     window: &'a Window
-) -> softbuffer::Surface<&'a Window, &'a Window> {
-    let context = softbuffer::Context::new(window).unwrap();
-    let mut surface = softbuffer::Surface::new(&context, window).unwrap();
-    surface.resize(
-        config.physical_width().try_into().unwrap(),
-        config.physical_height().try_into().unwrap()
-    ).unwrap();
-    surface
+) -> Result<softbuffer::Surface<&'a Window, &'a Window>, MrgrError> {
+    let context = softbuffer::Context::new(window)?;
+    let mut surface = softbuffer::Surface::new(&context, window)?;
+    softbuffer_resize(&mut surface, size)?;
+    Ok(surface)
 }
 
 pub fn skia_softbuffer_surface<'pixels>(
-    config: &MrgrConfig,
+    size: PhysicalSize<u32>,
     softbuffer_surface: &mut softbuffer::Surface<&Window, &Window>,
-) -> skia_safe::Borrows<'pixels, skia_safe::Surface> {
-    let size = config.physical_size();
-    let buf_ptr = softbuffer_surface.buffer_mut().unwrap().as_mut_ptr() as *mut u8;
+) -> Result<skia_safe::Borrows<'pixels, skia_safe::Surface>, MrgrError> {
+    let buf_ptr = softbuffer_surface.buffer_mut()?.as_mut_ptr() as *mut u8;
     let pixel_count = size.width as usize * size.height as usize;
     let buf = unsafe { slice::from_raw_parts_mut(buf_ptr, pixel_count * 4) };
     let info = skia_safe::ImageInfo::new(
@@ -63,41 +275,50 @@ pub fn skia_softbuffer_surface<'pixels>(
         skia_safe::AlphaType::Opaque,
         skia_safe::ColorSpace::new_srgb()
     );
-    skia_safe::surfaces::wrap_pixels(
-        &info, buf, size.width as usize * 4, None).unwrap()
+    skia_safe::surfaces::wrap_pixels(&info, buf, size.width as usize * 4, None)
+        .ok_or(MrgrError::SurfaceCreation("present"))
 }
 
-pub fn skia_raster_surface<'pixels>(config: &MrgrConfig) -> skia_safe::Surface {
+pub fn skia_raster_surface(config: &MrgrConfig) -> Result<skia_safe::Surface, MrgrError> {
     skia_safe::surfaces::raster_n32_premul(
-        (config.viewport_size.0 as i32, config.viewport_size.1 as i32)).unwrap()
+        (config.viewport_size.0 as i32, config.viewport_size.1 as i32))
+        .ok_or(MrgrError::SurfaceCreation("viewport"))
 }
 
-pub fn launch_softbuffer_skia_raster_runtime(config: MrgrConfig) {
-    let event_loop = EventLoopBuilder::<RuntimeEvent>::with_user_event()
-        .build().unwrap();
+pub fn launch_softbuffer_skia_raster_runtime(mut config: MrgrConfig) -> Result<(), MrgrError> {
+    let event_loop = EventLoopBuilder::<RuntimeEvent>::with_user_event().build()?;
     let proxy = event_loop.create_proxy();
-    let window = winit_create_window(&event_loop, &config);
+    let window = winit_create_window(&event_loop, &config)?;
+    window.set_fullscreen(config.fullscreen.to_winit(&window));
 
-    let mut sb_surface = softbuffer_create_context(&config, &window);
-    let mut present_surface = skia_softbuffer_surface(&config, &mut sb_surface);
-    let mut viewport_surface = Arc::new(Mutex::new(skia_raster_surface(&config)));
-    viewport_surface.lock().unwrap().canvas().clear(skia_safe::Color::WHITE);
-
-    let rt = Runtime {
-        proxy,
-        surface: viewport_surface.clone(),
-    };
-    unsafe { RT = Some(rt) }
+    let window_size = window.inner_size();
+    let mut sb_surface = softbuffer_create_context(window_size, &window)?;
+    let mut present_surface = skia_softbuffer_surface(window_size, &mut sb_surface)?;
+    let viewport_surface = install_runtime(
+        &config, RuntimeProxy::Window(proxy), Clock::Real(Instant::now()))?;
+    if let Some(monitor) = window.current_monitor() {
+        runtime().display.limit_to_monitor(config.viewport_size, monitor.size());
+    }
 
     let dur_frame = Duration::from_micros(1_000_000 / config.framerate as u64);
     let mut ins_last_frame = Instant::now() - dur_frame;
 
-    let present_bounds = {
-        let p_width = config.physical_width();
-        let p_height = config.physical_height();
-        skia_safe::Rect::new(0.0, 0.0, p_width as f32, p_height as f32)
-    };
-    let default_paint = skia_safe::Paint::default();
+    let mut present_bounds = present_bounds(&config, window_size);
+    let border_color = config.border_color.to_skia();
+    let mut mouse_position = None;
+    let mut exit_deadline = None;
+    let mut game = Some(config.take_game());
+    let mut game_thread = None;
+    let mut presented_serial = None;
+    let mut presented_filter = config.upscale_filter;
+    let mut presented_palette = None;
+    let mut presented_plane = None;
+    let mut filter_cache = FilterCache::default();
+    let mut overlay_visible = false;
+    let mut modifiers = ModifiersState::empty();
+    let mut pending_resize = None;
+    let mut outcome = Ok(());
+    let outcome_ref = &mut outcome;
 
     event_loop.run(move |event, window_target| {
         match event {
@@ -106,38 +327,90 @@ pub fn launch_softbuffer_skia_raster_runtime(config: MrgrConfig) {
                 StartCause::WaitCancelled { .. } => {}
                 StartCause::Poll => {}
                 StartCause::Init => {
-                    let jh = thread::spawn(move || {
-                        thread::park();
-                        (config.game_main)();
-                    });
-                    unsafe { GAME_THREAD = Some(jh.thread().id()); }
-                    jh.thread().unpark();
+                    game_thread = game.take().map(spawn_game_thread);
+                    #[cfg(feature = "gamepad")]
+                    crate::gamepad::spawn_gamepad_thread(config.gamepad_dead_zone);
                 }
             }
             Event::WindowEvent { event, .. } => match event {
+                WindowEvent::Resized(size) => {
+                    pending_resize = Some(size);
+                }
                 WindowEvent::Moved(_) => {}
-                WindowEvent::CloseRequested => {}
+                WindowEvent::CloseRequested if game_thread.is_none() => {
+                    window_target.exit();
+                }
+                WindowEvent::CloseRequested => {
+                    dispatch(MrgrEvent::ExitRequested);
+                    if exit_deadline.is_none() {
+                        exit_deadline = config.exit_timeout.map(|timeout| Instant::now() + timeout);
+                    }
+                }
                 WindowEvent::Destroyed => {}
                 WindowEvent::DroppedFile(_) => {}
                 WindowEvent::HoveredFile(_) => {}
                 WindowEvent::HoveredFileCancelled => {}
-                WindowEvent::Focused(_) => {}
+                WindowEvent::Focused(focused) => {
+                    dispatch(MrgrEvent::FocusChanged(focused));
+                }
                 WindowEvent::KeyboardInput { event, .. } => {
-                    let logical = event.logical_key;
+                    if let PhysicalKey::Code(code) = event.physical_key {
+                        // Only the press is intercepted, so a key held before Alt still gets its release.
+                        let pressed = event.state == ElementState::Pressed;
+                        if code == KeyCode::Enter && modifiers.alt_key() && pressed {
+                            if !event.repeat {
+                                pending_resize = toggle_fullscreen(&config, &window).or(pending_resize);
+                            }
+                        } else if config.stats_overlay_key == Some(code) {
+                            if event.state == ElementState::Pressed && !event.repeat {
+                                overlay_visible = !overlay_visible;
+                            }
+                        } else if let Some(key) = config.keymap.lookup(code).filter(|_| !event.repeat) {
+                            dispatch(match event.state {
+                                ElementState::Pressed => MrgrEvent::KeyPressed(key),
+                                ElementState::Released => MrgrEvent::KeyReleased(key),
+                            });
+                        }
+                    }
+                }
+                WindowEvent::ModifiersChanged(changed) => {
+                    modifiers = changed.state();
                 }
-                WindowEvent::ModifiersChanged(_) => {}
                 WindowEvent::Ime(_) => {}
-                WindowEvent::CursorMoved { .. } => {}
+                WindowEvent::CursorMoved { position, .. } => {
+                    let position = viewport_position(&config, &present_bounds, position);
+                    if mouse_position != Some(position) {
+                        mouse_position = Some(position);
+                        dispatch(MrgrEvent::MouseMoved(position.0, position.1));
+                    }
+                }
                 WindowEvent::CursorEntered { .. } => {}
                 WindowEvent::CursorLeft { .. } => {}
-                WindowEvent::MouseWheel { .. } => {}
-                WindowEvent::MouseInput { .. } => {
+                WindowEvent::MouseWheel { delta, .. } => {
+                    let (x, y) = match delta {
+                        MouseScrollDelta::LineDelta(x, y) => (x, y),
+                        MouseScrollDelta::PixelDelta(delta) => {
+                            let scale = present_bounds.width() as f64
+                                / config.viewport_size.0 as f64 * WHEEL_PIXELS_PER_LINE;
+                            ((delta.x / scale) as f32, (delta.y / scale) as f32)
+                        }
+                    };
+                    dispatch(MrgrEvent::MouseWheel(x, y));
+                }
+                WindowEvent::MouseInput { state, button, .. } => {
+                    if let Some(mouse) = mouse_button(button) {
+                        dispatch(match state {
+                            ElementState::Pressed => MrgrEvent::MousePressed(mouse),
+                            ElementState::Released => MrgrEvent::MouseReleased(mouse),
+                        });
+                    }
                 }
                 WindowEvent::AxisMotion { .. } => {}
                 WindowEvent::Touch(_) => {}
                 WindowEvent::Occluded(_) => {}
                 WindowEvent::RedrawRequested => {
                     ins_last_frame = Instant::now() - dur_frame;
+                    presented_serial = None;
                 }
                 _ => {}
             }
@@ -150,19 +423,101 @@ pub fn launch_softbuffer_skia_raster_runtime(config: MrgrConfig) {
                 DeviceEvent::Button { .. } => {}
                 DeviceEvent::Key(_) => {}
             }
-            Event::UserEvent(mrgr_event) => {}
+            Event::UserEvent(mrgr_event) => match mrgr_event {
+                RuntimeEvent::ShutdownRequested => {
+                    info!("Shutdown requested by the game thread, exiting.");
+                    runtime().frames.shutdown();
+                    window_target.exit();
+                }
+                RuntimeEvent::ZoomRequested(zoom_level) => {
+                    if window.fullscreen().is_none() {
+                        let size = zoomed_size(&config, zoom_level);
+                        pending_resize = window.request_inner_size(size).or(pending_resize);
+                    }
+                }
+            }
             Event::LoopExiting => {}
             _ => {}
         }
-        if ins_last_frame.elapsed() >= dur_frame {
-            ins_last_frame += dur_frame;
-            if let Ok(mut surface) = viewport_surface.try_lock() {
-                let image = surface.image_snapshot();
-                present_surface.canvas()
-                    .draw_image_rect(&image, None, &present_bounds, &default_paint);
-                sb_surface.buffer_mut().unwrap().present().unwrap();
+        if let Some(size) = pending_resize.take().filter(|size| size.width > 0 && size.height > 0) {
+            let resized = softbuffer_resize(&mut sb_surface, size)
+                .and_then(|_| skia_softbuffer_surface(size, &mut sb_surface));
+            match resized {
+                Ok(surface) => {
+                    present_surface = surface;
+                    present_bounds = self::present_bounds(&config, size);
+                    presented_serial = None;
+                }
+                Err(err) => {
+                    error!("Failed to resize the present surface: {err}");
+                    *outcome_ref = Err(err);
+                    window_target.exit();
+                }
+            }
+        }
+        if game_thread.as_ref().is_some_and(|jh| jh.is_finished()) {
+            if let Err(payload) = game_thread.take().unwrap().join() {
+                let message = panic_message(&*payload);
+                error!("Game thread panicked: {message}");
+                *outcome_ref = Err(MrgrError::GamePanicked(message.clone()));
+                if config.crash_screen {
+                    viewport_surface.clear_poison();
+                    draw_crash_screen(&config, &message);
+                } else {
+                    window_target.exit();
+                }
+            }
+        }
+        let lag = ins_last_frame.elapsed();
+        if lag >= dur_frame {
+            let frames = (lag.as_nanos() / dur_frame.as_nanos()) as u32;
+            ins_last_frame += dur_frame * frames;
+            runtime().stats.record_missed(frames as u64 - 1);
+            runtime().frames.tick(runtime().clock.elapsed());
+            let (image, serial) = {
+                let front = runtime().front.lock().unwrap();
+                (front.image.clone(), front.serial)
+            };
+            let filter = runtime().filter.get();
+            let palette = runtime().indexed.as_ref()
+                .map(|indexed| indexed.current(runtime().clock.elapsed()));
+            let plane = runtime().indexed.as_ref().map(|indexed| indexed.front_serial());
+            let stale = presented_serial == Some(serial)
+                && presented_filter == filter
+                && presented_palette == palette
+                && presented_plane == plane;
+            if !stale || overlay_visible || config.present_mode == PresentMode::Continuous {
+                let began = Instant::now();
+                let image = match (&runtime().indexed, &palette) {
+                    (Some(indexed), Some(palette)) => indexed.compose(&image, serial, palette),
+                    _ => image,
+                };
+                presented_serial = Some(serial);
+                presented_filter = filter;
+                presented_palette = palette;
+                presented_plane = plane;
+                present_surface.canvas().clear(border_color);
+                draw_upscaled(present_surface.canvas(), &image, &present_bounds, filter, &mut filter_cache);
+                if overlay_visible {
+                    let scale = present_bounds.width() / config.viewport_size.0 as f32;
+                    draw_overlay(present_surface.canvas(), &present_bounds, scale);
+                }
+                if let Err(err) = sb_surface.buffer_mut().and_then(|buffer| buffer.present()) {
+                    error!("Failed to present the frame: {err}");
+                    *outcome_ref = Err(err.into());
+                    window_target.exit();
+                }
+                runtime().stats.record_present(began);
+            }
+        }
+        if exit_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            warn!("Game thread did not acknowledge the exit request in time, forcing shutdown.");
+            if outcome_ref.is_ok() {
+                *outcome_ref = Err(MrgrError::ExitTimeout);
             }
+            window_target.exit();
         }
         window_target.set_control_flow(ControlFlow::WaitUntil(ins_last_frame + dur_frame))
-    }).unwrap();
+    })?;
+    outcome
 }
\ No newline at end of file
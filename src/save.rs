@@ -0,0 +1,244 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use log::warn;
+use crate::{Image, MrgrError};
+
+const SAVE_MAGIC: &[u8; 8] = b"MRGRSAVE";
+const SAVE_FORMAT_VERSION: u32 = 1;
+const SAVE_EXTENSION: &str = "sav";
+const HEADER_SIZE: usize = 8 + 4 + 8 + 8 + 4 + 4 + 8 + 4;
+const CHECKSUM_OFFSET: usize = HEADER_SIZE - 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveMetadata {
+    pub timestamp: SystemTime,
+    pub play_time: Duration,
+    pub thumbnail: Option<Image>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveSlot {
+    pub name: String,
+    pub metadata: SaveMetadata,
+}
+
+pub struct SaveStore {
+    dir: PathBuf,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Option<&'a [u8]> {
+        if self.bytes.len() < count { return None; }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Some(taken)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+}
+
+// The checksum covers the header up to the checksum field itself, followed by the body.
+fn checksum(header: &[u8], body: &[u8]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&header[..CHECKSUM_OFFSET]);
+    hasher.update(body);
+    hasher.finalize()
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> MrgrError {
+    MrgrError::AssetIo { path: path.display().to_string(), source }
+}
+
+fn encode(data: &[u8], play_time: Duration, thumbnail: Option<&Image>) -> Vec<u8> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let (thumb_width, thumb_height, thumb_pixels) = match thumbnail {
+        Some(image) => (image.width(), image.height(), image.pixels()),
+        None => (0, 0, &[][..]),
+    };
+    let mut body = Vec::with_capacity(thumb_pixels.len() + data.len());
+    body.extend_from_slice(thumb_pixels);
+    body.extend_from_slice(data);
+
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + body.len());
+    bytes.extend_from_slice(SAVE_MAGIC);
+    bytes.extend_from_slice(&SAVE_FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&timestamp.as_secs().to_le_bytes());
+    bytes.extend_from_slice(&(play_time.as_millis() as u64).to_le_bytes());
+    bytes.extend_from_slice(&thumb_width.to_le_bytes());
+    bytes.extend_from_slice(&thumb_height.to_le_bytes());
+    bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&checksum(&bytes, &body).to_le_bytes());
+    bytes.extend_from_slice(&body);
+    bytes
+}
+
+fn decode(slot: &str, bytes: &[u8]) -> Result<(SaveMetadata, Vec<u8>), MrgrError> {
+    let corrupted = |reason| MrgrError::SaveCorrupted { slot: slot.to_string(), reason };
+    let mut reader = Reader { bytes };
+    if reader.take(SAVE_MAGIC.len()) != Some(&SAVE_MAGIC[..]) {
+        return Err(corrupted("not a save file"));
+    }
+    let (
+        Some(version), Some(timestamp), Some(play_time),
+        Some(thumb_width), Some(thumb_height), Some(data_len), Some(stored_checksum)
+    ) = (
+        reader.u32(), reader.u64(), reader.u64(),
+        reader.u32(), reader.u32(), reader.u64(), reader.u32()
+    ) else {
+        return Err(corrupted("truncated header"));
+    };
+    if version != SAVE_FORMAT_VERSION {
+        return Err(corrupted("unsupported format version"));
+    }
+    if checksum(bytes, reader.bytes) != stored_checksum {
+        return Err(corrupted("checksum mismatch"));
+    }
+    // Both sizes come from the file, so a crafted header must not be able to overflow them.
+    let thumb_len = (thumb_width as usize).checked_mul(thumb_height as usize)
+        .and_then(|pixels| pixels.checked_mul(4));
+    let total_len = thumb_len.and_then(|thumb_len| (thumb_len as u64).checked_add(data_len));
+    let (Some(thumb_len), Some(total_len)) = (thumb_len, total_len) else {
+        return Err(corrupted("unexpected length"));
+    };
+    if reader.bytes.len() as u64 != total_len {
+        return Err(corrupted("unexpected length"));
+    }
+    let thumbnail = reader.take(thumb_len)
+        .filter(|pixels| !pixels.is_empty())
+        .map(|pixels| Image::new(thumb_width, thumb_height, pixels.to_vec()));
+    let metadata = SaveMetadata {
+        timestamp: UNIX_EPOCH + Duration::from_secs(timestamp),
+        play_time: Duration::from_millis(play_time),
+        thumbnail,
+    };
+    Ok((metadata, reader.bytes.to_vec()))
+}
+
+impl SaveStore {
+    pub fn open(app_name: &str) -> Result<Self, MrgrError> {
+        let dir = dirs::data_dir().ok_or(MrgrError::NoUserDirectory)?;
+        Ok(Self::at(dir.join(app_name).join("saves")))
+    }
+
+    pub fn at(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn slot_path(&self, slot: &str) -> Result<PathBuf, MrgrError> {
+        let valid = !slot.is_empty() && slot.chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+        if !valid {
+            return Err(MrgrError::InvalidSlotName(slot.to_string()));
+        }
+        Ok(self.dir.join(slot).with_extension(SAVE_EXTENSION))
+    }
+
+    pub fn write(
+        &self,
+        slot: &str,
+        data: &[u8],
+        play_time: Duration,
+        thumbnail: Option<&Image>
+    ) -> Result<(), MrgrError> {
+        let path = self.slot_path(slot)?;
+        fs::create_dir_all(&self.dir).map_err(|err| io_error(&self.dir, err))?;
+        let temporary = path.with_extension("tmp");
+        fs::write(&temporary, encode(data, play_time, thumbnail))
+            .map_err(|err| io_error(&temporary, err))?;
+        fs::rename(&temporary, &path).map_err(|err| io_error(&path, err))
+    }
+
+    pub fn read(&self, slot: &str) -> Result<(SaveMetadata, Vec<u8>), MrgrError> {
+        let path = self.slot_path(slot)?;
+        let bytes = fs::read(&path).map_err(|err| io_error(&path, err))?;
+        decode(slot, &bytes)
+    }
+
+    pub fn metadata(&self, slot: &str) -> Result<SaveMetadata, MrgrError> {
+        Ok(self.read(slot)?.0)
+    }
+
+    pub fn exists(&self, slot: &str) -> bool {
+        self.slot_path(slot).is_ok_and(|path| path.is_file())
+    }
+
+    pub fn delete(&self, slot: &str) -> Result<(), MrgrError> {
+        let path = self.slot_path(slot)?;
+        fs::remove_file(&path).map_err(|err| io_error(&path, err))
+    }
+
+    pub fn list(&self) -> Result<Vec<SaveSlot>, MrgrError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(io_error(&self.dir, err)),
+        };
+        let mut slots = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|err| io_error(&self.dir, err))?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SAVE_EXTENSION) { continue; }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else { continue; };
+            match self.metadata(name) {
+                Ok(metadata) => slots.push(SaveSlot { name: name.to_string(), metadata }),
+                Err(err) => warn!("Skipping save slot `{name}`: {err}"),
+            }
+        }
+        slots.sort_by(|a, b| b.metadata.timestamp.cmp(&a.metadata.timestamp));
+        Ok(slots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temporary_store(name: &str) -> SaveStore {
+        let dir = std::env::temp_dir().join(format!("mrgr-save-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        SaveStore::at(dir)
+    }
+
+    #[test]
+    fn round_trip() {
+        let store = temporary_store("round-trip");
+        let thumbnail = Image::new(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+        store.write("slot-1", b"progress", Duration::from_millis(1234), Some(&thumbnail)).unwrap();
+
+        let (metadata, data) = store.read("slot-1").unwrap();
+        assert_eq!(data, b"progress");
+        assert_eq!(metadata.play_time, Duration::from_millis(1234));
+        assert_eq!(metadata.thumbnail, Some(thumbnail));
+        assert_eq!(store.list().unwrap().len(), 1);
+        store.delete("slot-1").unwrap();
+        assert!(!store.exists("slot-1"));
+        let _ = fs::remove_dir_all(&store.dir);
+    }
+
+    #[test]
+    fn corruption_is_detected() {
+        let store = temporary_store("corruption");
+        store.write("slot", b"progress", Duration::ZERO, None).unwrap();
+        let path = store.slot_path("slot").unwrap();
+        let original = fs::read(&path).unwrap();
+
+        for offset in [SAVE_MAGIC.len() + 4, HEADER_SIZE] {
+            let mut bytes = original.clone();
+            bytes[offset] ^= 0xFF;
+            fs::write(&path, &bytes).unwrap();
+            let result = store.read("slot");
+            assert!(matches!(result, Err(MrgrError::SaveCorrupted { reason: "checksum mismatch", .. })));
+        }
+        let _ = fs::remove_dir_all(&store.dir);
+    }
+}
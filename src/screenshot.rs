@@ -0,0 +1,217 @@
+use std::fs;
+use crate::MrgrError;
+use crate::indexed::compose_viewport;
+use crate::resource::decode_image;
+use crate::runtime::runtime;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+fn rgba_info(width: u32, height: u32) -> skia_safe::ImageInfo {
+    skia_safe::ImageInfo::new(
+        (width as i32, height as i32),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::AlphaType::Unpremul,
+        None
+    )
+}
+
+impl Image {
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        assert_eq!(pixels.len(), width as usize * height as usize * 4,
+                   "Pixel buffer does not match the image size!");
+        Self { width, height, pixels }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        [self.pixels[index], self.pixels[index + 1], self.pixels[index + 2], self.pixels[index + 3]]
+    }
+
+    pub fn scaled(&self, factor: u32) -> Self {
+        let factor = factor.max(1);
+        let (width, height) = (self.width * factor, self.height * factor);
+        let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.extend_from_slice(&self.pixel(x / factor, y / factor));
+            }
+        }
+        Self { width, height, pixels }
+    }
+
+    pub(crate) fn from_skia(image: &skia_safe::Image) -> Option<Self> {
+        let (width, height) = (image.width() as u32, image.height() as u32);
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+        let read = image.read_pixels(
+            &rgba_info(width, height),
+            &mut pixels,
+            width as usize * 4,
+            (0, 0),
+            skia_safe::image::CachingHint::Allow
+        );
+        read.then_some(Self { width, height, pixels })
+    }
+
+    pub(crate) fn to_skia(&self) -> Option<skia_safe::Image> {
+        skia_safe::images::raster_from_data(
+            &rgba_info(self.width, self.height),
+            skia_safe::Data::new_copy(&self.pixels),
+            self.width as usize * 4
+        )
+    }
+
+    pub fn load_png(path: &str) -> Result<Self, MrgrError> {
+        Self::from_skia(&decode_image(path)?)
+            .ok_or_else(|| MrgrError::AssetDecoding { path: path.to_string() })
+    }
+
+    pub fn encode_png(&self) -> Option<Vec<u8>> {
+        let image = self.to_skia()?;
+        let data = image.encode(None, skia_safe::EncodedImageFormat::PNG, 100)?;
+        Some(data.as_bytes().to_vec())
+    }
+
+    pub fn save_png(&self, path: &str) -> Result<(), MrgrError> {
+        let data = self.encode_png()
+            .ok_or_else(|| MrgrError::AssetEncoding { path: path.to_string() })?;
+        fs::write(path, data)
+            .map_err(|source| MrgrError::AssetIo { path: path.to_string(), source })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub mismatched: usize,
+    pub max_difference: u8,
+    pub diff: Image,
+}
+
+impl Comparison {
+    pub fn is_match(&self) -> bool {
+        self.mismatched == 0
+    }
+}
+
+pub fn compare(actual: &Image, expected: &Image, tolerance: u8) -> Result<Comparison, MrgrError> {
+    if (actual.width, actual.height) != (expected.width, expected.height) {
+        return Err(MrgrError::ImageSizeMismatch {
+            expected: (expected.width, expected.height),
+            actual: (actual.width, actual.height),
+        });
+    }
+    let mut mismatched = 0;
+    let mut max_difference = 0;
+    let mut diff = Vec::with_capacity(actual.pixels.len());
+    for (a, e) in actual.pixels.chunks_exact(4).zip(expected.pixels.chunks_exact(4)) {
+        let difference = a.iter().zip(e)
+            .map(|(a, e)| a.abs_diff(*e))
+            .max()
+            .unwrap_or(0);
+        max_difference = max_difference.max(difference);
+        if difference > tolerance {
+            mismatched += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let gray = ((a[0] as u32 + a[1] as u32 + a[2] as u32) / 3 / 4) as u8;
+            diff.extend_from_slice(&[gray, gray, gray, 255]);
+        }
+    }
+    Ok(Comparison {
+        mismatched,
+        max_difference,
+        diff: Image::new(actual.width, actual.height, diff),
+    })
+}
+
+pub fn compare_with_reference(
+    actual: &Image,
+    reference_path: &str,
+    tolerance: u8,
+    diff_path: Option<&str>
+) -> Result<Comparison, MrgrError> {
+    let expected = Image::load_png(reference_path)?;
+    let comparison = compare(actual, &expected, tolerance)?;
+    if let Some(diff_path) = diff_path.filter(|_| !comparison.is_match()) {
+        comparison.diff.save_png(diff_path)?;
+    }
+    Ok(comparison)
+}
+
+pub fn screenshot() -> Result<Image, MrgrError> {
+    let (image, serial) = {
+        let front = runtime().front.lock().unwrap();
+        (front.image.clone(), front.serial)
+    };
+    Image::from_skia(&compose_viewport(image, serial)).ok_or(MrgrError::ViewportReadback)
+}
+
+pub fn save_screenshot(path: &str) -> Result<(), MrgrError> {
+    screenshot()?.save_png(path)
+}
+
+pub fn save_screenshot_zoomed(path: &str, zoom_level: u32) -> Result<(), MrgrError> {
+    screenshot()?.scaled(zoom_level).save_png(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, pixel: [u8; 4]) -> Image {
+        Image::new(width, height, pixel.repeat(width as usize * height as usize))
+    }
+
+    #[test]
+    fn scaling_repeats_pixels() {
+        let image = Image::new(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (4, 2));
+        assert_eq!(scaled.pixel(1, 1), [255, 0, 0, 255]);
+        assert_eq!(scaled.pixel(2, 0), [0, 0, 255, 255]);
+        assert_eq!(image.scaled(0), image);
+    }
+
+    #[test]
+    fn comparison_within_tolerance() {
+        let actual = solid(2, 2, [100, 100, 100, 255]);
+        let comparison = compare(&actual, &solid(2, 2, [102, 99, 100, 255]), 2).unwrap();
+        assert!(comparison.is_match());
+        assert_eq!(comparison.max_difference, 2);
+        assert_eq!(comparison.diff.pixel(0, 0), [25, 25, 25, 255]);
+    }
+
+    #[test]
+    fn comparison_over_tolerance() {
+        let mut pixels = [10, 10, 10, 255].repeat(2);
+        pixels[4] = 20;
+        let comparison = compare(&Image::new(2, 1, pixels), &solid(2, 1, [10, 10, 10, 255]), 5).unwrap();
+        assert!(!comparison.is_match());
+        assert_eq!(comparison.mismatched, 1);
+        assert_eq!(comparison.max_difference, 10);
+        assert_eq!(comparison.diff.pixel(0, 0), [2, 2, 2, 255]);
+        assert_eq!(comparison.diff.pixel(1, 0), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn comparison_size_mismatch() {
+        let result = compare(&solid(2, 2, [0, 0, 0, 255]), &solid(3, 2, [0, 0, 0, 255]), 0);
+        assert!(matches!(result, Err(MrgrError::ImageSizeMismatch { expected: (3, 2), actual: (2, 2) })));
+    }
+}
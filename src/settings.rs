@@ -0,0 +1,167 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use log::warn;
+use once_cell::sync::OnceCell;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::{config, FullscreenMode, Keymap, MrgrConfig, MrgrError, ScalingPolicy};
+
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+pub const SETTINGS_FILE_NAME: &str = "settings.toml";
+
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    path: PathBuf,
+    values: toml::Table,
+    window_settings_applied: bool,
+}
+
+static SETTINGS: OnceCell<Mutex<Settings>> = OnceCell::new();
+
+pub fn settings() -> Option<MutexGuard<'static, Settings>> {
+    SETTINGS.get().map(|settings| settings.lock().unwrap())
+}
+
+pub(crate) fn install_settings(settings: Settings) {
+    if let Err(settings) = SETTINGS.set(Mutex::new(settings)) {
+        *SETTINGS.get().unwrap().lock().unwrap() = settings.into_inner().unwrap();
+    }
+}
+
+impl Settings {
+    pub fn load(app_name: &str) -> Result<Self, MrgrError> {
+        let dir = dirs::config_dir().ok_or(MrgrError::NoUserDirectory)?;
+        Self::load_from(dir.join(app_name).join(SETTINGS_FILE_NAME))
+    }
+
+    pub fn load_from(path: impl Into<PathBuf>) -> Result<Self, MrgrError> {
+        let path = path.into();
+        let display = path.display().to_string();
+        let mut values = match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str::<toml::Table>(&text).map_err(|err| MrgrError::ConfigSyntax {
+                path: display.clone(),
+                message: err.message().to_string(),
+            })?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => toml::Table::new(),
+            Err(source) => return Err(MrgrError::AssetIo { path: display, source }),
+        };
+        let version = values.get(SCHEMA_VERSION_KEY)
+            .and_then(|version| version.as_integer())
+            .unwrap_or(SETTINGS_SCHEMA_VERSION as i64);
+        if version > SETTINGS_SCHEMA_VERSION as i64 {
+            return Err(MrgrError::UnsupportedSchema { path: display, version });
+        }
+        values.insert(SCHEMA_VERSION_KEY.to_string(), (SETTINGS_SCHEMA_VERSION as i64).into());
+        Ok(Self { path, values, window_settings_applied: false })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.values.get(key)?.clone();
+        match value.try_into() {
+            Ok(value) => Some(value),
+            Err(err) => {
+                warn!("Ignoring setting `{key}` with an unexpected type: {err}");
+                None
+            }
+        }
+    }
+
+    pub fn set<T: Serialize>(&mut self, key: &str, value: T) -> Result<(), MrgrError> {
+        let value = toml::Value::try_from(value)
+            .map_err(|err| MrgrError::SettingsEncoding(format!("`{key}`: {err}")))?;
+        self.values.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.values.remove(key);
+    }
+
+    pub fn save(&self) -> Result<(), MrgrError> {
+        let display = self.path.display().to_string();
+        let io_error = |source| MrgrError::AssetIo { path: display.clone(), source };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let text = toml::to_string(&self.values)
+            .map_err(|err| MrgrError::SettingsEncoding(err.to_string()))?;
+        let temporary = self.path.with_extension("toml.tmp");
+        fs::write(&temporary, text).map_err(io_error)?;
+        fs::rename(&temporary, &self.path).map_err(io_error)
+    }
+
+    // Applied at most once, so `mrgr_launch` does not undo overrides applied on top of them.
+    pub(crate) fn apply_window_settings(&mut self, config: &mut MrgrConfig) {
+        if self.window_settings_applied { return; }
+        self.window_settings_applied = true;
+        if let Some(zoom_level) = self.get::<u32>("zoom_level") {
+            if (config::MINIMUM_ZOOM_LEVEL..=config::MAXIMUM_ZOOM_LEVEL).contains(&zoom_level) {
+                config.zoom_level = zoom_level;
+            } else {
+                warn!("Ignoring saved zoom level {zoom_level}, it is out of range.");
+            }
+        }
+        if let Some(fullscreen) = self.get::<FullscreenMode>("fullscreen") {
+            config.fullscreen = fullscreen;
+        }
+        if let Some(scaling) = self.get::<ScalingPolicy>("scaling") {
+            config.scaling = scaling;
+        }
+        if let Some(keymap) = self.get::<Keymap>("keymap") {
+            config.keymap = keymap;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temporary_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mrgr-settings-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join(SETTINGS_FILE_NAME)
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = temporary_path("round-trip");
+        let mut settings = Settings::load_from(&path).unwrap();
+        assert_eq!(settings.get::<u32>("zoom_level"), None);
+        settings.set("zoom_level", 3u32).unwrap();
+        settings.set("fullscreen", FullscreenMode::Exclusive).unwrap();
+        settings.save().unwrap();
+
+        let loaded = Settings::load_from(&path).unwrap();
+        assert_eq!(loaded.get::<u32>("zoom_level"), Some(3));
+        assert_eq!(loaded.get::<FullscreenMode>("fullscreen"), Some(FullscreenMode::Exclusive));
+        assert_eq!(loaded.get::<i64>(SCHEMA_VERSION_KEY), Some(SETTINGS_SCHEMA_VERSION as i64));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn newer_schema_is_rejected() {
+        let path = temporary_path("newer-schema");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, format!("{SCHEMA_VERSION_KEY} = {}\n", SETTINGS_SCHEMA_VERSION + 1)).unwrap();
+        let result = Settings::load_from(&path);
+        assert!(matches!(result, Err(MrgrError::UnsupportedSchema { version, .. })
+            if version == SETTINGS_SCHEMA_VERSION as i64 + 1));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn wrong_type_is_ignored() {
+        let mut settings = Settings::load_from(temporary_path("wrong-type")).unwrap();
+        settings.set("zoom_level", "large").unwrap();
+        assert_eq!(settings.get::<u32>("zoom_level"), None);
+        assert_eq!(settings.get::<String>("zoom_level"), Some("large".to_string()));
+    }
+}
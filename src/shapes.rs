@@ -0,0 +1,104 @@
+use crate::{Canvas, Color};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ShapeStyle {
+    #[default]
+    Fill,
+    Stroke,
+}
+
+fn rect(pos: (i32, i32), size: (i32, i32)) -> skia_safe::Rect {
+    skia_safe::Rect::from_xywh(pos.0 as f32, pos.1 as f32, size.0 as f32, size.1 as f32)
+}
+
+fn center(pos: (i32, i32)) -> skia_safe::Point {
+    skia_safe::Point::new(pos.0 as f32 + 0.5, pos.1 as f32 + 0.5)
+}
+
+impl Canvas {
+    pub fn set_antialias(&mut self, antialias: bool) {
+        self.antialias = antialias;
+    }
+
+    fn shape_paint(&self, color: Color, style: ShapeStyle) -> skia_safe::Paint {
+        let mut paint = skia_safe::Paint::default();
+        paint.set_color(color.to_skia());
+        paint.set_anti_alias(self.antialias);
+        match style {
+            ShapeStyle::Fill => paint.set_style(skia_safe::PaintStyle::Fill),
+            ShapeStyle::Stroke => paint.set_style(skia_safe::PaintStyle::Stroke).set_stroke_width(0.0),
+        };
+        paint
+    }
+
+    // Strokes are hairlines through pixel centers, so outlines stay inside the given bounds.
+    fn shape_rect(pos: (i32, i32), size: (i32, i32), style: ShapeStyle) -> skia_safe::Rect {
+        match style {
+            ShapeStyle::Fill => rect(pos, size),
+            ShapeStyle::Stroke => skia_safe::Rect::from_xywh(
+                pos.0 as f32 + 0.5, pos.1 as f32 + 0.5, (size.0 - 1) as f32, (size.1 - 1) as f32),
+        }
+    }
+
+    pub fn clear(&mut self, color: impl Into<Color>) {
+        self.surface.canvas().clear(color.into().to_skia());
+    }
+
+    pub fn pixel(&mut self, pos: (i32, i32), color: impl Into<Color>) {
+        self.fill_rect(pos, (1, 1), color);
+    }
+
+    pub fn fill_rect(&mut self, pos: (i32, i32), size: (i32, i32), color: impl Into<Color>) {
+        let paint = self.shape_paint(color.into(), ShapeStyle::Fill);
+        self.surface.canvas().draw_rect(rect(pos, size), &paint);
+    }
+
+    pub fn stroke_rect(&mut self, pos: (i32, i32), size: (i32, i32), color: impl Into<Color>) {
+        let paint = self.shape_paint(color.into(), ShapeStyle::Stroke);
+        self.surface.canvas().draw_rect(Self::shape_rect(pos, size, ShapeStyle::Stroke), &paint);
+    }
+
+    pub fn line(&mut self, from: (i32, i32), to: (i32, i32), color: impl Into<Color>) {
+        let color = color.into();
+        let paint = self.shape_paint(color, ShapeStyle::Stroke);
+        self.surface.canvas().draw_line(center(from), center(to), &paint);
+        self.pixel(to, color);
+    }
+
+    pub fn circle(&mut self, center: (i32, i32), radius: i32, color: impl Into<Color>, style: ShapeStyle) {
+        let pos = (center.0 - radius, center.1 - radius);
+        let size = (radius * 2 + 1, radius * 2 + 1);
+        self.ellipse(pos, size, color, style);
+    }
+
+    pub fn ellipse(&mut self, pos: (i32, i32), size: (i32, i32), color: impl Into<Color>, style: ShapeStyle) {
+        let paint = self.shape_paint(color.into(), style);
+        self.surface.canvas().draw_oval(Self::shape_rect(pos, size, style), &paint);
+    }
+
+    pub fn rounded_rect(
+        &mut self,
+        pos: (i32, i32),
+        size: (i32, i32),
+        radius: i32,
+        color: impl Into<Color>,
+        style: ShapeStyle
+    ) {
+        let paint = self.shape_paint(color.into(), style);
+        let bounds = Self::shape_rect(pos, size, style);
+        self.surface.canvas().draw_round_rect(bounds, radius as f32, radius as f32, &paint);
+    }
+
+    pub fn polygon(&mut self, points: &[(i32, i32)], color: impl Into<Color>, style: ShapeStyle) {
+        if points.is_empty() { return; }
+        let points: Vec<_> = points.iter()
+            .map(|point| match style {
+                ShapeStyle::Fill => skia_safe::Point::new(point.0 as f32, point.1 as f32),
+                ShapeStyle::Stroke => center(*point),
+            })
+            .collect();
+        let path = skia_safe::Path::polygon(&points, true, None, None);
+        let paint = self.shape_paint(color.into(), style);
+        self.surface.canvas().draw_path(&path, &paint);
+    }
+}
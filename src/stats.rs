@@ -0,0 +1,82 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::runtime::runtime;
+use crate::TextStyle;
+use crate::graphics::draw_text;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct FrameStats {
+    pub frame_time: Duration,
+    pub present_time: Duration,
+    pub paint_time: Duration,
+    pub missed_frames: u64,
+    pub fps: f32,
+}
+
+pub(crate) struct StatsTracker {
+    stats: Mutex<FrameStats>,
+    last_present: Mutex<Option<Instant>>,
+    window: Mutex<(Instant, u32)>,
+}
+
+impl StatsTracker {
+    pub fn new() -> Self {
+        Self {
+            stats: Mutex::new(FrameStats::default()),
+            last_present: Mutex::new(None),
+            window: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    pub fn record_paint(&self, duration: Duration) {
+        self.stats.lock().unwrap().paint_time = duration;
+    }
+
+    pub fn record_missed(&self, frames: u64) {
+        self.stats.lock().unwrap().missed_frames += frames;
+    }
+
+    pub fn record_present(&self, began: Instant) {
+        let now = Instant::now();
+        let mut stats = self.stats.lock().unwrap();
+        stats.present_time = now - began;
+        if let Some(last) = self.last_present.lock().unwrap().replace(began) {
+            stats.frame_time = began - last;
+        }
+        let mut window = self.window.lock().unwrap();
+        window.1 += 1;
+        let elapsed = now - window.0;
+        if elapsed >= Duration::from_secs(1) {
+            stats.fps = window.1 as f32 / elapsed.as_secs_f32();
+            *window = (now, 0);
+        }
+    }
+
+    pub fn snapshot(&self) -> FrameStats {
+        *self.stats.lock().unwrap()
+    }
+}
+
+pub fn frame_stats() -> FrameStats {
+    runtime().stats.snapshot()
+}
+
+pub(crate) fn draw_overlay(canvas: &skia_safe::Canvas, bounds: &skia_safe::Rect, scale: f32) {
+    let stats = frame_stats();
+    let text = format!(
+        "FPS {:.1}\nFrame {:.2}ms\nPaint {:.2}ms\nPresent {:.2}ms\nMissed {}",
+        stats.fps,
+        stats.frame_time.as_secs_f32() * 1000.0,
+        stats.paint_time.as_secs_f32() * 1000.0,
+        stats.present_time.as_secs_f32() * 1000.0,
+        stats.missed_frames
+    );
+    let mut backdrop = skia_safe::Paint::default();
+    backdrop.set_color(skia_safe::Color::from_argb(160, 0, 0, 0));
+    canvas.save();
+    canvas.translate((bounds.left, bounds.top));
+    canvas.scale((scale, scale));
+    canvas.draw_rect(skia_safe::Rect::from_xywh(0.0, 0.0, 128.0, 84.0), &backdrop);
+    draw_text(canvas, &text, TextStyle::SmallNormal, (2, 2), 124);
+    canvas.restore();
+}
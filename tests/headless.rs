@@ -0,0 +1,35 @@
+use mrgr::*;
+
+struct Flash {
+    pressed: bool,
+}
+
+impl Game for Flash {
+    fn on_event(&mut self, event: &Event) {
+        if *event == Event::KeyPressed(Key::Start) {
+            self.pressed = true;
+        }
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas, _alpha: f32) {
+        canvas.clear(if self.pressed { Color::RED } else { Color::BLUE });
+    }
+}
+
+#[test]
+fn scripted_input_reaches_the_viewport() -> Result<(), MrgrError> {
+    let mut headless = Headless::launch(MrgrConfig {
+        viewport_size: (128, 72),
+        game: Box::new(Flash { pressed: false }),
+        ..MrgrConfig::default()
+    })?;
+    headless.step_frames(4);
+    assert_eq!(headless.pixel(64, 36)?, [0, 0, 255, 255]);
+
+    assert!(matches!(Headless::launch(MrgrConfig::default()), Err(MrgrError::RuntimeActive)));
+
+    headless.send(Event::KeyPressed(Key::Start));
+    headless.step_frames(2);
+    assert_eq!(headless.pixel(64, 36)?, [255, 0, 0, 255]);
+    headless.finish()
+}